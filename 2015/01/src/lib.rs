@@ -0,0 +1,46 @@
+use common::input::{self, InputError};
+use common::solution::Solution;
+use std::fmt::Display;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    // Each parenthesis moves Santa one floor up or down.
+    type Input = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse_with(input.trim(), "", |c| match c {
+            "(" => Ok(1),
+            ")" => Ok(-1),
+            _ => Err("invalid character"),
+        })
+    }
+
+    fn part1(steps: &Self::Input) -> impl Display {
+        count_unmatched_parenthese(steps)
+    }
+
+    fn part2(steps: &Self::Input) -> impl Display {
+        index_of_invalid_parenthese_close(steps)
+            .map(|i| (i + 1).to_string())
+            .unwrap_or_else(|| "Did not enter basement".to_string())
+    }
+}
+
+pub fn count_unmatched_parenthese(steps: &[i32]) -> i32 {
+    steps.iter().sum()
+}
+
+pub fn index_of_invalid_parenthese_close(steps: &[i32]) -> Option<usize> {
+    let mut count = 0;
+
+    for (i, step) in steps.iter().enumerate() {
+        count += step;
+
+        if count < 0 {
+            return Some(i);
+        }
+    }
+
+    None
+}
@@ -0,0 +1,61 @@
+use common::input::{self, InputError, Separator};
+use common::solution::Solution;
+use std::cmp::{max, min};
+use std::fmt::Display;
+
+pub struct Dimensions(u32, u32, u32);
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Dimensions>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse_with(input, Separator::Lines, parse_to_dimensions)
+    }
+
+    fn part1(presents: &Self::Input) -> impl Display {
+        presents.iter().map(calc_wrapping_paper).sum::<u32>()
+    }
+
+    fn part2(presents: &Self::Input) -> impl Display {
+        presents.iter().map(calc_ribbon_length).sum::<u32>()
+    }
+}
+
+pub fn parse_to_dimensions(s: &str) -> Result<Dimensions, String> {
+    let d = s
+        .split('x')
+        .map(|n| n.parse::<u32>().map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match d[..] {
+        [l, w, h] => Ok(Dimensions(l, w, h)),
+        _ => Err("expected three dimensions".to_string()),
+    }
+}
+
+pub fn calc_wrapping_paper(d: &Dimensions) -> u32 {
+    let lw = d.0 * d.1;
+    let wh = d.1 * d.2;
+    let hl = d.2 * d.0;
+
+    let min = min(min(lw, wh), min(wh, hl));
+
+    (2 * lw) + (2 * wh) + (2 * hl) + min
+}
+
+pub fn calc_ribbon_length(d: &Dimensions) -> u32 {
+    let a = min(min(d.0, d.1), min(d.1, d.2));
+    let b = {
+        if d.0 >= min(d.1, d.2) && d.0 <= max(d.1, d.2) {
+            d.0
+        } else if d.1 >= min(d.0, d.2) && d.1 <= max(d.0, d.2) {
+            d.1
+        } else {
+            d.2
+        }
+    };
+
+    (2 * a) + (2 * b) + (d.0 * d.1 * d.2)
+}
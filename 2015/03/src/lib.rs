@@ -0,0 +1,65 @@
+use common::input::{self, InputError};
+use common::point::{Direction, Point2};
+use common::solution::Solution;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+type Position = Point2<i32>;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Direction>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse_with(input.trim(), "", |c| {
+            c.chars()
+                .next()
+                .and_then(Direction::from_arrow)
+                .ok_or("invalid character")
+        })
+    }
+
+    fn part1(moves: &Self::Input) -> impl Display {
+        calc_visits(moves)
+    }
+
+    fn part2(moves: &Self::Input) -> impl Display {
+        calc_alternating_visits(moves)
+    }
+}
+
+pub fn calc_visits(moves: &[Direction]) -> u32 {
+    let mut position = Position::default();
+    let mut visited = HashMap::<Position, u32>::new();
+    visited.insert(position, 1);
+
+    for m in moves {
+        position += m.offset();
+
+        *visited.entry(position).or_insert(0) += 1;
+    }
+
+    visited.values().len() as u32
+}
+
+pub fn calc_alternating_visits(moves: &[Direction]) -> u32 {
+    let mut a_position = Position::default();
+    let mut b_position = Position::default();
+    let mut visited = HashMap::<Position, u32>::new();
+    visited.insert(a_position, 1);
+
+    for (i, m) in moves.iter().enumerate() {
+        let position = match i % 2 {
+            0 => &mut a_position,
+            1 => &mut b_position,
+            _ => unreachable!(),
+        };
+
+        *position += m.offset();
+
+        *visited.entry(*position).or_insert(0) += 1;
+    }
+
+    visited.values().len() as u32
+}
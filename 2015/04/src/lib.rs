@@ -0,0 +1,44 @@
+use common::input::InputError;
+use common::solution::Solution;
+use std::fmt::Display;
+
+// the masks are for the upper 5 and 6 nibbles
+pub const FIVE_ZEROS: u128 =
+    u128::from_le_bytes([255, 255, 240, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+pub const SIX_ZEROS: u128 =
+    u128::from_le_bytes([255, 255, 255, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    // the secret key
+    type Input = String;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(key: &Self::Input) -> impl Display {
+        find_hash(key, FIVE_ZEROS).0
+    }
+
+    fn part2(key: &Self::Input) -> impl Display {
+        find_hash(key, SIX_ZEROS).0
+    }
+}
+
+pub fn find_hash(s: &str, mask: u128) -> (u32, u128) {
+    let mut nonce = 0;
+    let mut hash = u128::MAX;
+
+    while hash & mask != 0 {
+        nonce += 1;
+        hash = u128::from_le_bytes(md5::compute(combine_with_nonce(s, nonce)).0);
+    }
+
+    (nonce, hash)
+}
+
+fn combine_with_nonce(s: &str, nonce: u32) -> Vec<u8> {
+    format!("{}{}", s, nonce).as_bytes().to_vec()
+}
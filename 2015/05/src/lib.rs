@@ -0,0 +1,90 @@
+use common::input::{self, InputError, Separator};
+use common::solution::Solution;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        // part two looks at pairs, so every string needs at least two letters
+        input::parse_with(input, Separator::Lines, |l| match l.len() {
+            0 | 1 => Err("string is too short"),
+            _ => Ok(l.to_string()),
+        })
+    }
+
+    fn part1(strings: &Self::Input) -> impl Display {
+        strings.iter().filter(|s| is_nice_part_one(s)).count()
+    }
+
+    fn part2(strings: &Self::Input) -> impl Display {
+        strings.iter().filter(|s| is_nice_part_two(s)).count()
+    }
+}
+
+pub fn is_nice_part_one(s: &str) -> bool {
+    let mut double = false;
+    let mut bad_substring = false;
+    let mut last = s.chars().next().unwrap();
+    let mut vowels = is_vowel(last) as u32;
+
+    for c in s.chars().skip(1) {
+        if is_vowel(c) {
+            vowels += 1;
+        }
+
+        match (last, c) {
+            ('a', 'b') | ('c', 'd') | ('p', 'q') | ('x', 'y') => bad_substring = true,
+            _ => (),
+        }
+
+        if last == c {
+            double = true;
+        }
+
+        last = c;
+    }
+
+    vowels >= 3 && double && !bad_substring
+}
+
+pub fn is_nice_part_two(s: &str) -> bool {
+    let mut repeat = false;
+    let mut last_last = s.chars().next().unwrap();
+    let mut last = s.chars().nth(1).unwrap();
+    let mut last_pair = (last_last, last);
+    let mut pairs = HashMap::<(char, char), u32>::new();
+    pairs.insert(last_pair, 1);
+
+    for c in s.chars().skip(2) {
+        let current_pair = (last, c);
+
+        if current_pair != last_pair {
+            *pairs.entry(current_pair).or_insert(0) += 1
+        }
+
+        if last_last == c {
+            repeat = true;
+        }
+
+        last_pair = current_pair;
+        last_last = last;
+        last = c;
+    }
+
+    let not_overlapping_pairs = pairs
+        .values()
+        .cloned()
+        .filter(|v| *v >= 2)
+        .collect::<Vec<u32>>()
+        .len();
+
+    not_overlapping_pairs > 0 && repeat
+}
+
+fn is_vowel(c: char) -> bool {
+    matches!(c, 'a' | 'e' | 'i' | 'o' | 'u')
+}
@@ -0,0 +1,123 @@
+use common::grid::Grid;
+use common::input::{self, InputError, Separator};
+use common::solution::Solution;
+use std::fmt::Display;
+
+const SIZE: usize = 1000;
+
+#[derive(Clone, Debug)]
+pub enum Instruction {
+    On(Section),
+    Off(Section),
+    Toggle(Section),
+}
+
+#[derive(Clone, Debug)]
+pub struct Section(usize, usize, usize, usize);
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse_with(input, Separator::Lines, parse_light_config)
+    }
+
+    fn part1(instructions: &Self::Input) -> impl Display {
+        let mut grid = Grid::new(SIZE, SIZE, false);
+
+        for i in instructions {
+            apply_instruction_part_1(i, &mut grid);
+        }
+
+        count_lights(&grid)
+    }
+
+    fn part2(instructions: &Self::Input) -> impl Display {
+        let mut grid = Grid::new(SIZE, SIZE, 0);
+
+        for i in instructions {
+            apply_instruction_part_2(i, &mut grid);
+        }
+
+        sum_intensity(&grid)
+    }
+}
+
+fn apply_instruction_part_1(instruction: &Instruction, grid: &mut Grid<bool>) {
+    let mut apply = |s: &Section, f: fn(bool) -> bool| {
+        for y in s.1..=s.3 {
+            for light in &mut grid.row_mut(y)[s.0..=s.2] {
+                *light = f(*light);
+            }
+        }
+    };
+
+    match instruction {
+        Instruction::On(s) => apply(s, |_| true),
+        Instruction::Off(s) => apply(s, |_| false),
+        Instruction::Toggle(s) => apply(s, |b: bool| !b),
+    }
+}
+
+fn apply_instruction_part_2(instruction: &Instruction, grid: &mut Grid<u32>) {
+    let mut apply = |s: &Section, f: fn(u32) -> u32| {
+        for y in s.1..=s.3 {
+            for light in &mut grid.row_mut(y)[s.0..=s.2] {
+                *light = f(*light);
+            }
+        }
+    };
+
+    match instruction {
+        Instruction::On(s) => apply(s, |i| i + 1),
+        Instruction::Off(s) => apply(s, |i| if i == 0 { 0 } else { i - 1 }),
+        Instruction::Toggle(s) => apply(s, |i| i + 2),
+    }
+}
+
+fn parse_light_config(config: &str) -> Result<Instruction, String> {
+    let config: Vec<&str> = config.split_whitespace().collect();
+
+    match config[..] {
+        ["turn", "on", ref s @ ..] => Ok(Instruction::On(parse_section(s)?)),
+        ["turn", "off", ref s @ ..] => Ok(Instruction::Off(parse_section(s)?)),
+        ["toggle", ref s @ ..] => Ok(Instruction::Toggle(parse_section(s)?)),
+        _ => Err("unknown instruction".to_string()),
+    }
+}
+
+fn parse_section(config: &[&str]) -> Result<Section, String> {
+    let corner = |s: &str| -> Result<(usize, usize), String> {
+        let mut n = s
+            .split(',')
+            .map(|n| n.parse::<usize>().map_err(|e| e.to_string()));
+
+        match (n.next(), n.next(), n.next()) {
+            (Some(x), Some(y), None) => Ok((x?, y?)),
+            _ => Err(format!("invalid corner {:?}", s)),
+        }
+    };
+
+    match *config {
+        [first, "through", second] => {
+            let ((x0, y0), (x1, y1)) = (corner(first)?, corner(second)?);
+
+            // sections are inclusive
+            match x0 <= x1 && y0 <= y1 && x1 < SIZE && y1 < SIZE {
+                true => Ok(Section(x0, y0, x1, y1)),
+                false => Err("section is outside the grid".to_string()),
+            }
+        }
+        _ => Err("expected <x,y> through <x,y>".to_string()),
+    }
+}
+
+fn count_lights(grid: &Grid<bool>) -> u32 {
+    grid.iter().map(|(_, l)| *l as u32).sum()
+}
+
+fn sum_intensity(grid: &Grid<u32>) -> u32 {
+    grid.iter().map(|(_, l)| l).sum()
+}
@@ -0,0 +1,148 @@
+use common::input::{self, InputError, Separator};
+use common::solution::Solution;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[derive(Clone, Debug)]
+pub enum Value {
+    Constant(u16),
+    Wire(String),
+}
+
+#[derive(Clone, Debug)]
+pub enum Op {
+    Assignment(Value),
+    Not(Value),
+    And(Value, Value),
+    Or(Value, Value),
+    Shl(Value, Value),
+    Shr(Value, Value),
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    // the op driving each wire
+    type Input = HashMap<String, Op>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        Ok(input::parse_with(input, Separator::Lines, read_op)?
+            .into_iter()
+            .collect())
+    }
+
+    fn part1(map: &Self::Input) -> impl Display {
+        process_ops(&mut map.clone())["a"]
+    }
+
+    // Overrides wire `b` with the part 1 answer and runs the circuit again.
+    fn part2(map: &Self::Input) -> impl Display {
+        let mut map = map.clone();
+        let a = process_ops(&mut map.clone())["a"];
+
+        map.insert("b".to_string(), Op::Assignment(Value::Constant(a)));
+        process_ops(&mut map)["a"]
+    }
+}
+
+fn process_ops(map: &mut HashMap<String, Op>) -> HashMap<String, u16> {
+    let mut ret = HashMap::<String, u16>::new();
+
+    while !map.is_empty() {
+        for (k, v) in map.clone() {
+            match v {
+                Op::Assignment(v) => uniary_op(&mut ret, map, k, v, |a| a),
+                Op::Not(v) => uniary_op(&mut ret, map, k, v, |a| !a),
+                Op::And(a, b) => binary_op(&mut ret, map, k, a, b, |a, b| a & b),
+                Op::Or(a, b) => binary_op(&mut ret, map, k, a, b, |a, b| a | b),
+                Op::Shl(a, b) => binary_op(&mut ret, map, k, a, b, |a, b| a << b),
+                Op::Shr(a, b) => binary_op(&mut ret, map, k, a, b, |a, b| a >> b),
+            }
+        }
+    }
+
+    ret
+}
+
+fn uniary_op(
+    ret: &mut HashMap<String, u16>,
+    map: &mut HashMap<String, Op>,
+    k: String,
+    v: Value,
+    uniary_op: fn(u16) -> u16,
+) {
+    match v {
+        Value::Constant(n) => {
+            ret.entry(k.clone()).or_insert(uniary_op(n));
+            map.remove(&k);
+        }
+        Value::Wire(w) => {
+            if let Some(a) = ret.clone().get(&w) {
+                ret.insert(k.clone(), uniary_op(*a));
+                map.remove(&k);
+            }
+        }
+    }
+}
+
+fn binary_op(
+    ret: &mut HashMap<String, u16>,
+    map: &mut HashMap<String, Op>,
+    k: String,
+    a: Value,
+    b: Value,
+    binary_op: fn(u16, u16) -> u16,
+) {
+    let left = match a {
+        Value::Constant(n) => Some(n),
+        Value::Wire(w) => ret.get(&w).copied(),
+    };
+
+    let right = match b {
+        Value::Constant(n) => Some(n),
+        Value::Wire(w) => ret.get(&w).copied(),
+    };
+
+    if let (Some(x), Some(y)) = (left, right) {
+        ret.insert(k.clone(), binary_op(x, y));
+        map.remove(&k);
+    }
+}
+
+fn read_op(line: &str) -> Result<(String, Op), String> {
+    let line: Vec<&str> = line.split_whitespace().collect();
+
+    let op = match line[..] {
+        [v, "->", _] => Op::Assignment(v.into()),
+        ["NOT", v, "->", _] => match v.into() {
+            Value::Constant(_) => return Err("invalid NOT".to_string()),
+            v => Op::Not(v),
+        },
+        [a, op, b, "->", _] => {
+            let left: Value = a.into();
+            let right: Value = b.into();
+
+            match (op, &right) {
+                ("AND", _) => Op::And(left, right),
+                ("OR", _) => Op::Or(left, right),
+                ("LSHIFT", Value::Constant(_)) => Op::Shl(left, right),
+                ("RSHIFT", Value::Constant(_)) => Op::Shr(left, right),
+                ("LSHIFT", _) => return Err("invalid shl".to_string()),
+                ("RSHIFT", _) => return Err("invalid shr".to_string()),
+                _ => return Err("invalid op".to_string()),
+            }
+        }
+        _ => return Err("invalid op".to_string()),
+    };
+
+    Ok((line[line.len() - 1].to_string(), op))
+}
+
+impl std::convert::From<&str> for Value {
+    fn from(s: &str) -> Self {
+        match s.parse::<u16>() {
+            Ok(n) => Value::Constant(n),
+            Err(_) => Value::Wire(s.to_string()),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use common::input::{self, InputError, Separator};
+use common::solution::Solution;
+use std::fmt::Display;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse_with(input, Separator::Lines, |l| {
+            validate(l).map(|_| l.to_string())
+        })
+    }
+
+    fn part1(lines: &Self::Input) -> impl Display {
+        lines.iter().map(|l| char_diff(l)).sum::<u32>()
+    }
+
+    fn part2(lines: &Self::Input) -> impl Display {
+        lines.iter().map(|l| new_repr(l)).sum::<u32>()
+    }
+}
+
+// Checks `line` is a quoted string literal whose escapes are `\\`, `\"` or
+// `\x` and two hex digits, which the counting below relies on.
+fn validate(line: &str) -> Result<(), &'static str> {
+    let inner = line
+        .strip_prefix('"')
+        .and_then(|l| l.strip_suffix('"'))
+        .ok_or("expected a quoted string")?;
+    let mut iter = inner.chars();
+
+    while let Some(c) = iter.next() {
+        match c {
+            '\\' => match iter.next() {
+                Some('\\') | Some('"') => (),
+                Some('x') => {
+                    let hex = iter.by_ref().take(2).filter(char::is_ascii_hexdigit);
+                    if hex.count() != 2 {
+                        return Err("expected two hex digits after \\x");
+                    }
+                }
+                _ => return Err("invalid escape"),
+            },
+            '"' => return Err("unescaped quote"),
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn char_diff(line: &str) -> u32 {
+    let repr = line.len() as u32;
+    let mut data = 0;
+    let mut iter = line.chars();
+
+    while let Some(c) = iter.next() {
+        match c {
+            // `validate` made sure there's always another character following
+            // an initial backslash :-).
+            '\\' => match iter.next().unwrap() {
+                '\\' | '"' => data += 1,
+                'x' => {
+                    data += 1;
+                    iter.next();
+                    iter.next();
+                }
+                _ => unreachable!(),
+            },
+            '"' => (),
+            _ => data += 1,
+        }
+    }
+
+    repr - data
+}
+
+fn new_repr(line: &str) -> u32 {
+    let data = line.len() as u32;
+    let mut new = 0;
+    let mut iter = line.chars();
+
+    while let Some(c) = iter.next() {
+        match c {
+            '\\' => match iter.next().unwrap() {
+                '\\' | '"' => new += 4,
+                'x' => {
+                    new += 5;
+                    iter.next();
+                    iter.next();
+                }
+                _ => unreachable!(),
+            },
+            '"' => new += 2,
+            _ => new += 1,
+        }
+    }
+
+    new - data + 2
+}
@@ -0,0 +1,41 @@
+use common::input::{self, InputError};
+use common::solution::Solution;
+use std::fmt::Display;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    // the mass of each module
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse(input, '\n')
+    }
+
+    fn part1(modules: &Self::Input) -> impl Display {
+        modules.iter().copied().map(calc_fuel).sum::<u32>()
+    }
+
+    fn part2(modules: &Self::Input) -> impl Display {
+        modules
+            .iter()
+            .copied()
+            .map(recursively_calc_fuel)
+            .sum::<u32>()
+    }
+}
+
+fn calc_fuel(n: u32) -> u32 {
+    (n / 3) - 2
+}
+
+fn recursively_calc_fuel(n: u32) -> u32 {
+    if n == 0 {
+        return n;
+    }
+
+    let (n, overflow) = (n / 3).overflowing_sub(2);
+    let n = if !overflow { n } else { 0 };
+
+    n + recursively_calc_fuel(n)
+}
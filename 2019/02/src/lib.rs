@@ -0,0 +1,77 @@
+use common::input::{self, InputError};
+use common::solution::Solution;
+use std::fmt::Display;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse(input, ',')
+    }
+
+    fn part1(ops: &Self::Input) -> impl Display {
+        let mut ops = ops.clone();
+
+        // specific initialization
+        ops[1] = 12;
+        ops[2] = 2;
+
+        process_ops(&ops)[0]
+    }
+
+    fn part2(ops: &Self::Input) -> impl Display {
+        let (noun, verb) = find_inputs(ops);
+        100 * noun + verb
+    }
+}
+
+fn find_inputs(ops: &[usize]) -> (usize, usize) {
+    let mut ret;
+    let mut mem;
+
+    for i in 0..ops.len() {
+        for j in 0..ops.len() {
+            mem = ops.to_vec();
+            mem[1] = i;
+            mem[2] = j;
+            mem = process_ops(&mem);
+            ret = mem[0];
+
+            if ret == 19690720 {
+                return (i, j);
+            }
+        }
+    }
+
+    panic!("invalid program");
+}
+
+fn process_ops(ops: &[usize]) -> Vec<usize> {
+    let mut i = 0;
+    let mut ops = ops.to_vec();
+
+    while i < ops.len() {
+        match ops[i] {
+            1 | 2 => {
+                if let [x, y, p] = ops[i + 1..=i + 3] {
+                    ops[p] = op(ops[i], ops[x], ops[y]);
+                    i += 4;
+                }
+            }
+            99 => return ops.to_vec(),
+            _ => panic!("invalid sequence"),
+        }
+    }
+
+    panic!("invalid program");
+}
+
+fn op(n: usize, x: usize, y: usize) -> usize {
+    match n {
+        1 => x + y,
+        2 => x * y,
+        _ => unreachable!(),
+    }
+}
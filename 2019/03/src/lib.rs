@@ -0,0 +1,94 @@
+use common::grid::SparseGrid;
+use common::input::{self, InputError, Separator};
+use common::point::{Direction, Point2};
+use common::solution::Solution;
+use std::cmp::min;
+use std::convert::TryInto;
+use std::fmt::Display;
+
+// The steps each wire takes to reach a cell.
+type Grid = SparseGrid<u32>;
+
+const ORIGIN: Point2<i64> = Point2::new(0, 0);
+
+// A straight run of a wire.
+pub struct Op {
+    pub direction: Direction,
+    pub length: u32,
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    // the path of each wire
+    type Input = [Vec<Op>; 2];
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        let wires = input::parse_with(input, Separator::Lines, parse)?;
+
+        wires
+            .try_into()
+            .map_err(|_| InputError::Invalid("expected two wires".to_string()))
+    }
+
+    fn part1([a, b]: &Self::Input) -> impl Display {
+        solve(&build_grid(a), &build_grid(b)).0
+    }
+
+    fn part2([a, b]: &Self::Input) -> impl Display {
+        solve(&build_grid(a), &build_grid(b)).1
+    }
+}
+
+fn solve(a: &Grid, b: &Grid) -> (u32, u32) {
+    let mut closest = u32::MAX;
+    let mut steps = u32::MAX;
+
+    for (p, av) in a.iter() {
+        match b.get(p) {
+            Some(bv) if p != ORIGIN => {
+                let d = p.manhattan(ORIGIN) as u32;
+                closest = min(d, closest);
+                steps = min(av + bv, steps);
+            }
+            _ => (),
+        }
+    }
+
+    (closest, steps)
+}
+
+fn build_grid(ops: &[Op]) -> Grid {
+    let mut map = Grid::new();
+    let mut position = ORIGIN;
+    let mut steps = 0;
+
+    for op in ops {
+        for _ in 0..op.length {
+            steps += 1;
+            position += op.direction.offset();
+            map.insert(position, steps);
+        }
+    }
+
+    map
+}
+
+fn parse(ops: &str) -> Result<Vec<Op>, String> {
+    let mut ret = vec![];
+    for op in ops.split(',') {
+        let mut chars = op.chars();
+        let direction = chars
+            .next()
+            .and_then(Direction::from_letter)
+            .ok_or_else(|| format!("invalid direction in {:?}", op))?;
+        let length = chars
+            .as_str()
+            .parse()
+            .map_err(|_| format!("invalid length in {:?}", op))?;
+
+        ret.push(Op { direction, length });
+    }
+
+    Ok(ret)
+}
@@ -0,0 +1,101 @@
+use common::input::InputError;
+use common::solution::Solution;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    // The input is a range of passwords, such as `246515-739105`.
+    type Input = RangeInclusive<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        let invalid = || InputError::Invalid(format!("{:?} is not a range", input.trim()));
+        let (lo, hi) = input.trim().split_once('-').ok_or_else(invalid)?;
+
+        match (lo.parse(), hi.parse()) {
+            (Ok(lo), Ok(hi)) => Ok(lo..=hi),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn part1(range: &Self::Input) -> impl Display {
+        range.clone().filter(|v| is_valid(*v)).count()
+    }
+
+    fn part2(range: &Self::Input) -> impl Display {
+        range
+            .clone()
+            .filter(|v| is_valid(*v) && exactly_two(*v))
+            .count()
+    }
+}
+
+fn exactly_two(n: u32) -> bool {
+    let n: Vec<u32> = n
+        .to_string()
+        .chars()
+        .map(|c| c.to_string().parse().unwrap())
+        .collect();
+
+    let len = n.len();
+    let first_two = n[0] == n[1] && n[1] != n[2];
+    let last_two = n[len - 1] == n[len - 2] && n[len - 2] != n[len - 3];
+
+    if first_two || last_two {
+        return true;
+    }
+
+    let mut double = false;
+
+    for i in 3..n.len() {
+        if n[i - 3] != n[i - 2] && n[i - 2] == n[i - 1] && n[i] != n[i - 1] {
+            double = true;
+        }
+    }
+
+    double
+}
+
+fn is_valid(n: u32) -> bool {
+    if !(100000..=999999).contains(&n) {
+        return false;
+    }
+
+    let n: Vec<u32> = n
+        .to_string()
+        .chars()
+        .map(|v| v.to_string().parse().unwrap())
+        .collect();
+
+    let mut flag = false;
+    let mut last = 0u32;
+    for d in n {
+        if d < last {
+            return false;
+        }
+
+        if d == last {
+            flag = true;
+        }
+
+        last = d;
+    }
+
+    flag
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert!(is_valid(111111));
+        assert!(!exactly_two(111111));
+        assert!(exactly_two(112233));
+        assert!(!exactly_two(123444));
+        assert!(exactly_two(111122));
+        assert!(exactly_two(331111));
+    }
+}
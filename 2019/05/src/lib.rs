@@ -0,0 +1,31 @@
+use common::input::{self, InputError};
+use common::solution::Solution;
+use std::fmt::Display;
+use vm::{Int, Vm};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Vec<Int>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse(input, ',')
+    }
+
+    fn part1(code: &Self::Input) -> impl Display {
+        diagnostic(code, 1)
+    }
+
+    fn part2(code: &Self::Input) -> impl Display {
+        diagnostic(code, 5)
+    }
+}
+
+// Runs the diagnostic program for the system with the given id.
+fn diagnostic(code: &[Int], id: Int) -> Int {
+    let mut vm = Vm::new(code);
+
+    vm.run_from_start(&[id])
+        .and_then(|r| r.diagnostic())
+        .unwrap()
+}
@@ -0,0 +1,89 @@
+use common::input::{self, InputError};
+use common::solution::Solution;
+use std::fmt::Display;
+use vm::{Int, Status, Vm};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<Int>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse(input, ',')
+    }
+
+    fn part1(code: &Self::Input) -> impl Display {
+        max_signal(code, vec![0, 1, 2, 3, 4])
+    }
+
+    fn part2(code: &Self::Input) -> impl Display {
+        max_signal(code, vec![5, 6, 7, 8, 9])
+    }
+}
+
+// Tries every ordering of the phase settings, returning the strongest signal
+// sent to the thrusters.
+fn max_signal(code: &[Int], phases: Vec<i64>) -> i64 {
+    heaps_alg(phases)
+        .into_iter()
+        .map(|p| run(code, p))
+        .max()
+        .unwrap()
+}
+
+fn heaps_alg(v: Vec<i64>) -> Vec<Vec<i64>> {
+    fn f(len: usize, v: &mut Vec<i64>, ret: &mut Vec<Vec<i64>>) {
+        if len == 0 || len == 1 {
+            ret.push(v.to_owned());
+            return;
+        }
+
+        for i in 0..len - 1 {
+            f(len - 1, v, ret);
+            let j = if len.is_multiple_of(2) { i } else { 0 };
+            v.swap(j, len - 1);
+        }
+
+        f(len - 1, v, ret);
+    }
+
+    let mut ret = vec![];
+    let mut v = v;
+
+    f(v.len(), &mut v, &mut ret);
+
+    ret
+}
+
+fn run(code: &[i64], p: Vec<i64>) -> i64 {
+    let simulate_thruster = |vm: &mut Vm, input: i64, last: i64| -> i64 {
+        loop {
+            match vm.step() {
+                Ok(Status::Output(o)) => break o,
+                Ok(Status::AwaitingInput) => vm.insert_input(input),
+                Ok(Status::Halted) => break last,
+                _ => continue,
+            }
+        }
+    };
+
+    let mut vm = vec![Vm::new(code); 5];
+    let mut ret = [0; 5];
+    let mut i = 0;
+
+    for (i, v) in vm.iter_mut().enumerate() {
+        v.insert_input(p[i]);
+    }
+
+    loop {
+        ret[i % 5] = simulate_thruster(&mut vm[i % 5], ret[(4 + i) % 5], ret[i % 5]);
+
+        if vm[i % 5].halted() {
+            break;
+        }
+
+        i += 1;
+    }
+
+    ret[4]
+}
@@ -0,0 +1,123 @@
+use common::input::{self, InputError};
+use common::ocr;
+use common::solution::Solution;
+use std::fmt::Display;
+
+const WIDTH: usize = 25;
+const HEIGHT: usize = 6;
+const AREA: usize = WIDTH * HEIGHT;
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    // the pixels of every layer, one after another
+    type Input = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        let pixels: Vec<usize> = input::parse(input.trim(), "")?;
+
+        match pixels.len() % AREA {
+            0 => Ok(pixels),
+            _ => Err(InputError::Invalid(format!(
+                "{} pixels don't make whole {}x{} layers",
+                pixels.len(),
+                WIDTH,
+                HEIGHT
+            ))),
+        }
+    }
+
+    fn part1(pixels: &Self::Input) -> impl Display {
+        checksum(pixels, AREA)
+    }
+
+    fn part2(pixels: &Self::Input) -> impl Display {
+        let image = show(&combine_layers(pixels, AREA), WIDTH);
+
+        // the picture itself is the answer if it can't be read
+        ocr::parse(&image).unwrap_or_else(|e| format!("{}\n{}", e, image))
+    }
+}
+
+fn checksum(input: &[usize], area: usize) -> usize {
+    let layers = input.chunks(area);
+
+    let least_zeros = layers
+        .min_by(|x, y| {
+            let x_0s = x.iter().filter(|x| **x == 0).count();
+            let y_0s = y.iter().filter(|x| **x == 0).count();
+            x_0s.cmp(&y_0s)
+        })
+        .unwrap()
+        .to_vec();
+
+    let ones = least_zeros.iter().filter(|n| **n == 1).count();
+    let twos = least_zeros.iter().filter(|n| **n == 2).count();
+
+    ones * twos
+}
+
+fn combine_layers(input: &[usize], area: usize) -> Vec<usize> {
+    input
+        .iter()
+        .enumerate()
+        .rev()
+        .fold(vec![2; area], |mut acc, (i, n)| {
+            acc[i % area] = match n {
+                0 => 0,
+                1 => 1,
+                _ => acc[i % area],
+            };
+
+            acc
+        })
+}
+
+fn show(input: &[usize], width: usize) -> String {
+    input
+        .iter()
+        .map(|n| match n {
+            1 => '#',
+            _ => ' ',
+        })
+        .collect::<Vec<char>>()
+        .chunks(width)
+        .map(|line| format!("{}\n", line.iter().collect::<String>()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let picture = [
+            "#  # #   ##   # ##  #### ",
+            "#  # #   ##   ##  #    # ",
+            "####  # #  # # #  #   #  ",
+            "#  #   #    #  ####  #   ",
+            "#  #   #    #  #  # #    ",
+            "#  #   #    #  #  # #### ",
+        ];
+
+        // a transparent layer over the picture
+        let input: String = std::iter::repeat_n('2', AREA)
+            .chain(
+                picture
+                    .concat()
+                    .chars()
+                    .map(|c| if c == '#' { '1' } else { '0' }),
+            )
+            .collect();
+
+        let pixels = Day08::parse(&input).unwrap();
+        assert_eq!(Day08::part2(&pixels).to_string(), "HYYAZ");
+    }
+
+    #[test]
+    fn puzzle() {
+        let pixels = Day08::parse(include_str!("../input/input.txt")).unwrap();
+        assert_eq!(Day08::part2(&pixels).to_string(), "UGCUH");
+    }
+}
@@ -0,0 +1,28 @@
+use common::input::{self, InputError};
+use common::solution::Solution;
+use std::fmt::Display;
+use vm::{Int, Vm};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Int>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse(input, ',')
+    }
+
+    fn part1(code: &Self::Input) -> impl Display {
+        boost(code, 1)
+    }
+
+    fn part2(code: &Self::Input) -> impl Display {
+        boost(code, 2)
+    }
+}
+
+fn boost(code: &[Int], mode: Int) -> Int {
+    let mut vm = Vm::new(code);
+
+    vm.run_from_start(&[mode]).unwrap().last_output().unwrap()
+}
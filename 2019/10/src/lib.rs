@@ -0,0 +1,226 @@
+use common::grid::Grid;
+use common::input::InputError;
+use common::point::Point2;
+use common::solution::Solution;
+use ordered_float::OrderedFloat;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+type Rad = OrderedFloat<f64>;
+
+#[derive(Clone, PartialEq)]
+enum Element {
+    Empty,
+    Astroid,
+}
+
+// An astroid's column and row on the map, as x and y.
+type Position = Point2<i64>;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    // every astroid, with the others grouped by the angle they're seen at
+    type Input = HashMap<Position, HashMap<Rad, Vec<Position>>>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        let grid = Grid::parse(input, |c| match c {
+            '.' => Ok(Element::Empty),
+            '#' => Ok(Element::Astroid),
+            _ => Err("expected '.' or '#'"),
+        })?;
+
+        Ok(map_astroids(&grid))
+    }
+
+    fn part1(map: &Self::Input) -> impl Display {
+        solve1(map)
+    }
+
+    fn part2(map: &Self::Input) -> impl Display {
+        solve2(map, 200)
+    }
+}
+
+fn solve1(map: &HashMap<Position, HashMap<Rad, Vec<Position>>>) -> usize {
+    let mut best = 0;
+
+    for p in map.values() {
+        if best < p.len() {
+            best = p.len();
+        }
+    }
+
+    best
+}
+
+fn solve2(map: &HashMap<Position, HashMap<Rad, Vec<Position>>>, rounds: usize) -> usize {
+    let station = map.iter().max_by(|x, y| x.1.len().cmp(&y.1.len())).unwrap();
+    let sp = *station.0;
+    let mut sm = station.1.clone();
+    let mut angles: Vec<Rad> = sm.keys().cloned().collect();
+
+    // sort largest to smallest
+    angles.sort_by(|a, b| {
+        let a: f64 = (*a).into();
+        let b: f64 = (*b).into();
+        b.partial_cmp(&a).unwrap()
+    });
+
+    // Q1 => 0 < r <= pi/2
+    let first: Vec<Rad> = angles
+        .clone()
+        .into_iter()
+        .filter(|r| OrderedFloat(0f64) < *r && *r <= OrderedFloat(std::f64::consts::FRAC_PI_2))
+        .collect();
+
+    // Q2 => pi / 2 < r <= pi
+    let second: Vec<Rad> = angles
+        .clone()
+        .into_iter()
+        .filter(|r| {
+            OrderedFloat(std::f64::consts::FRAC_PI_2) < *r
+                && *r <= OrderedFloat(std::f64::consts::PI)
+        })
+        .collect();
+
+    // Q3 & Q4 => -pi < r <= 0
+    let three_four: Vec<Rad> = angles
+        .clone()
+        .into_iter()
+        .filter(|r| OrderedFloat(-std::f64::consts::PI) < *r && *r <= OrderedFloat(0f64))
+        .collect();
+
+    let mut rads = vec![];
+
+    rads.extend(first);
+    rads.extend(three_four);
+    rads.extend(second);
+
+    let mut i = 0;
+    let mut vaporized = Position::default();
+
+    for _ in 0..rounds {
+        let angle = &rads[i % rads.len()];
+        let astroids = sm.get_mut(angle).unwrap();
+
+        // bad to do this each, but ¯\_(ツ)_/¯
+        astroids.sort_by_key(|a| (sp.y - a.y) + (a.x - sp.x));
+
+        vaporized = astroids.remove(0);
+
+        if astroids.is_empty() {
+            rads.remove(i % rads.len());
+
+            // don't overflow a usize
+            if i == 0 {
+                i = rads.len() - 1;
+            } else {
+                i -= 1
+            }
+        }
+
+        i = (i + 1) % rads.len();
+    }
+
+    (vaporized.x * 100 + vaporized.y) as usize
+}
+
+fn map_astroids_to_angle(p: Position, astroids: &[Position]) -> HashMap<Rad, Vec<Position>> {
+    let mut angles: HashMap<Rad, Vec<Position>> = HashMap::new();
+
+    for a in astroids {
+        // relative to `p`, with y pointing up
+        let x = (a.x - p.x) as f64;
+        let y = (p.y - a.y) as f64;
+
+        let rad = y.atan2(x);
+
+        let angle = angles.entry(rad.into()).or_insert(vec![]);
+        angle.push(*a);
+    }
+
+    angles
+}
+
+fn map_astroids(grid: &Grid<Element>) -> HashMap<Position, HashMap<Rad, Vec<Position>>> {
+    let astroids: Vec<Position> = grid
+        .iter()
+        .filter(|(_, v)| **v == Element::Astroid)
+        .map(|((x, y), _)| Position::new(x as i64, y as i64))
+        .collect();
+
+    let mut mem: HashMap<Position, HashMap<Rad, Vec<Position>>> = HashMap::new();
+    for p in astroids.clone() {
+        mem.insert(p, map_astroids_to_angle(p, &astroids));
+    }
+
+    mem
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BIG: &str = r#".#..##.###...#######
+##.############..##.
+.#.######.########.#
+.###.#######.####.#.
+#####.##.#.##.###.##
+..#####..#.#########
+####################
+#.####....###.#.#.##
+##.#################
+#####.##.###..####..
+..######..##.#######
+####.##.####...##..#
+.#####..#.######.###
+##...#.##########...
+#.##########.#######
+.####.#.###.###.#.##
+....##.##.###..#####
+.#.#.###########.###
+#.#.#.#####.####.###
+###.##.####.##.#..##"#;
+
+    #[test]
+    fn part2_simple_example() {
+        let input: Vec<String> = [".#..#", ".....", "#####", "....#", "...##"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let map = Day10::parse(&input.join("\n")).unwrap();
+        assert_eq!(solve1(&map), 8);
+    }
+
+    #[test]
+    fn part1_larger_example() {
+        let input: Vec<String> = BIG.split('\n').map(|s| s.to_string()).collect();
+        let map = Day10::parse(&input.join("\n")).unwrap();
+        assert_eq!(solve1(&map), 210);
+    }
+
+    #[test]
+    fn part2_smaller_example() {
+        let input: Vec<String> = r#".#....#####...#..
+##...##.#####..##
+##...#...#.#####.
+..#.....#...###..
+..#.#.....#....##"#
+            .split('\n')
+            .map(|s| s.to_string())
+            .collect();
+
+        let map = Day10::parse(&input.join("\n")).unwrap();
+        assert_eq!(solve2(&map, 36), 1303);
+    }
+
+    #[test]
+    fn part2_larger_example() {
+        let input: Vec<String> = BIG.split('\n').map(|s| s.to_string()).collect();
+
+        let map = Day10::parse(&input.join("\n")).unwrap();
+        assert_eq!(solve2(&map, 200), 802);
+    }
+}
@@ -0,0 +1,295 @@
+use common::grid::SparseGrid;
+use common::point::Point2;
+use std::io::{self, Write};
+use vm::Int;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    // Parses `rrggbb`, with or without a leading `#`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.strip_prefix('#').unwrap_or(s);
+        if s.len() != 6 || !s.is_ascii() {
+            return None;
+        }
+
+        let c = |i: usize| u8::from_str_radix(&s[i..i + 2], 16).ok();
+        Some(Rgb(c(0)?, c(2)?, c(4)?))
+    }
+
+    fn luma(self) -> u8 {
+        let Rgb(r, g, b) = self;
+        ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Pbm,
+    Pgm,
+    Png,
+}
+
+impl Format {
+    pub fn from_path(path: &str) -> Option<Self> {
+        match path.rsplit('.').next()?.to_ascii_lowercase().as_ref() {
+            "pbm" => Some(Format::Pbm),
+            "pgm" => Some(Format::Pgm),
+            "png" => Some(Format::Png),
+            _ => None,
+        }
+    }
+}
+
+pub struct Options {
+    pub scale: usize,
+    pub white: Rgb,
+    pub black: Rgb,
+    pub flip_y: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            scale: 1,
+            white: Rgb::WHITE,
+            black: Rgb::BLACK,
+            flip_y: false,
+        }
+    }
+}
+
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    // Rasterizes the hull over the bounding box of every panel the robot
+    // touched, or `None` if it touched none. Row 0 is the smallest y unless
+    // `flip_y` is set.
+    pub fn from_hull(hull: &SparseGrid<Int>, opts: &Options) -> Option<Self> {
+        let scale = opts.scale.max(1);
+        let bounds = hull.bounds()?;
+
+        let (w, h) = (bounds.width(), bounds.height());
+        let mut pixels = Vec::with_capacity(w * h * scale * scale);
+
+        for row in 0..h * scale {
+            let y = match opts.flip_y {
+                true => bounds.max_y - (row / scale) as i64,
+                false => bounds.min_y + (row / scale) as i64,
+            };
+
+            for col in 0..w * scale {
+                let x = bounds.min_x + (col / scale) as i64;
+
+                pixels.push(match hull.get(Point2::new(x, y)) {
+                    Some(1) => opts.white,
+                    _ => opts.black,
+                });
+            }
+        }
+
+        Some(Self {
+            width: w * scale,
+            height: h * scale,
+            pixels,
+        })
+    }
+
+    pub fn write<W: Write>(&self, w: W, format: Format) -> io::Result<()> {
+        match format {
+            Format::Pbm => self.write_pbm(w),
+            Format::Pgm => self.write_pgm(w),
+            Format::Png => self.write_png(w),
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Rgb]> {
+        self.pixels.chunks(self.width)
+    }
+
+    // Binary PBM. Set bits are black, so dark colors become 1.
+    fn write_pbm<W: Write>(&self, mut w: W) -> io::Result<()> {
+        write!(w, "P4\n{} {}\n", self.width, self.height)?;
+
+        for row in self.rows() {
+            let mut bytes = vec![0u8; self.width.div_ceil(8)];
+
+            for (i, p) in row.iter().enumerate() {
+                if p.luma() < 128 {
+                    bytes[i / 8] |= 0x80 >> (i % 8);
+                }
+            }
+
+            w.write_all(&bytes)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_pgm<W: Write>(&self, mut w: W) -> io::Result<()> {
+        write!(w, "P5\n{} {}\n255\n", self.width, self.height)?;
+
+        let bytes: Vec<u8> = self.pixels.iter().map(|p| p.luma()).collect();
+        w.write_all(&bytes)
+    }
+
+    // 8-bit RGB PNG. The image data goes in uncompressed deflate blocks, which
+    // keeps this dependency-free at the cost of file size.
+    fn write_png<W: Write>(&self, mut w: W) -> io::Result<()> {
+        w.write_all(b"\x89PNG\r\n\x1a\n")?;
+
+        let mut header = vec![];
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        chunk(&mut w, b"IHDR", &header)?;
+
+        // every scanline starts with filter type 0
+        let mut raw = Vec::with_capacity(self.height * (1 + 3 * self.width));
+        for row in self.rows().take(self.height) {
+            raw.push(0);
+            for &Rgb(r, g, b) in row {
+                raw.extend_from_slice(&[r, g, b]);
+            }
+        }
+
+        chunk(&mut w, b"IDAT", &zlib_stored(&raw))?;
+        chunk(&mut w, b"IEND", &[])
+    }
+}
+
+fn chunk<W: Write>(w: &mut W, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+
+    let crc = crc32(kind.iter().chain(data));
+    w.write_all(&crc.to_be_bytes())
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const BLOCK: usize = 0xffff;
+
+    let mut out = vec![0x78, 0x01];
+    let blocks = data.chunks(BLOCK).collect::<Vec<_>>();
+
+    if blocks.is_empty() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let len = block.len() as u16;
+
+        out.push((i + 1 == blocks.len()) as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32<'a, I: IntoIterator<Item = &'a u8>>(bytes: I) -> u32 {
+    let mut crc = !0u32;
+
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+
+    for &x in bytes {
+        a = (a + x as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hull() -> SparseGrid<Int> {
+        // a white panel above a black one
+        let mut hull = SparseGrid::new();
+        hull.insert(Point2::new(0, 1), 1);
+        hull.insert(Point2::new(0, 0), 0);
+        hull
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn pbm() {
+        let opts = Options {
+            scale: 2,
+            ..Options::default()
+        };
+
+        let mut buf = vec![];
+        Image::from_hull(&hull(), &opts)
+            .unwrap()
+            .write(&mut buf, Format::Pbm)
+            .unwrap();
+
+        assert_eq!(buf, b"P4\n2 4\n\xc0\xc0\x00\x00");
+    }
+
+    #[test]
+    fn pgm_flipped() {
+        let opts = Options {
+            white: Rgb::parse("#808080").unwrap(),
+            flip_y: true,
+            ..Options::default()
+        };
+
+        let mut buf = vec![];
+        Image::from_hull(&hull(), &opts)
+            .unwrap()
+            .write(&mut buf, Format::Pgm)
+            .unwrap();
+
+        assert_eq!(buf, b"P5\n1 2\n255\n\x80\x00");
+    }
+
+    #[test]
+    fn png() {
+        let mut buf = vec![];
+        Image::from_hull(&hull(), &Options::default())
+            .unwrap()
+            .write(&mut buf, Format::Png)
+            .unwrap();
+
+        assert!(buf.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"));
+        assert!(buf.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn empty_hull() {
+        assert!(Image::from_hull(&SparseGrid::new(), &Options::default()).is_none());
+    }
+}
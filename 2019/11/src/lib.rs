@@ -0,0 +1,56 @@
+use common::grid::SparseGrid;
+use common::input::{self, InputError};
+use common::ocr;
+use common::solution::Solution;
+use std::fmt::Display;
+use vm::robot::{Action, Robot};
+use vm::{Int, Vm};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Int>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse(input, ',')
+    }
+
+    fn part1(code: &Self::Input) -> impl Display {
+        paint(code, 0).len()
+    }
+
+    fn part2(code: &Self::Input) -> impl Display {
+        let image = render(&paint(code, 1));
+
+        // the picture itself is the answer if it can't be read
+        ocr::parse(&image).unwrap_or_else(|e| format!("{}\n{}", e, image))
+    }
+}
+
+// Runs the painter starting on a panel of the given color. Every panel starts
+// black (0); the program reads the color underneath it, then answers with a
+// color to paint and a direction to turn before moving forward.
+pub fn paint(code: &[Int], initial: Int) -> SparseGrid<Int> {
+    let mut robot = Robot::new(Vm::new(code), 0)
+        .sensor(|_, color| *color)
+        .protocol(2, |out| {
+            Ok(vec![
+                Action::Set(out[0]),
+                Action::turn(out[1])?,
+                Action::Forward(1),
+            ])
+        });
+
+    robot.apply(Action::Set(initial));
+    robot.run().unwrap();
+    robot.grid
+}
+
+// Draws the painted panels. The robot starts facing up, towards smaller y, so
+// the smallest y goes on the top row.
+pub fn render(hull: &SparseGrid<Int>) -> String {
+    hull.render(|c| match c {
+        Some(1) => '#',
+        _ => ' ',
+    })
+}
@@ -1,130 +1,82 @@
-use common::load_input;
-use std::collections::HashMap;
-use vm::{Int, Status, Vm};
+mod image;
 
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-struct Position {
-    x: i64,
-    y: i64,
-}
+use aoc2019_day11::{paint, render, Day11};
+use common::grid::SparseGrid;
+use common::read_input;
+use common::solution::{run, Solution};
+use image::{Format, Image, Options, Rgb};
+use std::fs::File;
+use std::io::BufWriter;
+use vm::Int;
 
-struct Bot {
-    d: Direction,
-    p: Position,
-}
+const USAGE: &str = "usage: day11 [--image <file>] [--scale <n>] [--white <rrggbb>]
+             [--black <rrggbb>] [--flip-y]
 
-impl Bot {
-    pub fn raw_turn(&mut self, n: i64) {
-        self.d = match n {
-            0 => match self.d {
-                Direction::U => Direction::L,
-                Direction::D => Direction::R,
-                Direction::L => Direction::D,
-                Direction::R => Direction::U,
-            },
-            1 => match self.d {
-                Direction::U => Direction::R,
-                Direction::D => Direction::L,
-                Direction::L => Direction::U,
-                Direction::R => Direction::D,
-            },
-            _ => panic!("invalid turn: {}", n),
-        }
-    }
+  --image   draw the part 2 registration and save it as .pbm, .pgm or .png
+  --scale   pixels per panel (default 1)
+  --white   color for white panels (default ffffff)
+  --black   color for black and unpainted panels (default 000000)
+  --flip-y  put the largest y at the top, mirroring the registration
 
-    pub fn walk(&mut self, n: i64) {
-        match self.d {
-            Direction::U => self.p.y += n,
-            Direction::D => self.p.y -= n,
-            Direction::L => self.p.x -= n,
-            Direction::R => self.p.x += n,
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
-enum Direction {
-    U,
-    D,
-    L,
-    R,
-}
+The input is the file named by AOC_INPUT, or else the input file next to the
+crate, and is read from stdin when AOC_INPUT is - or there's no such file.";
 
 fn main() {
-    let code: Vec<Int> = load_input!(",", Int);
-    let mut part1: HashMap<Position, Int> = HashMap::new();
-    let mut part2: HashMap<Position, Int> = HashMap::new();
-
-    paint(&code, &mut part1, 0);
-    paint(&code, &mut part2, 1);
-    display(&part2);
-
-    println!("painted at least: {}", part1.len());
-}
-
-fn paint(code: &[Int], map: &mut HashMap<Position, Int>, initial: Int) {
-    let mut vm = Vm::new(&code);
-    vm.insert_input(initial);
-
-    let mut current = Bot {
-        d: Direction::U,
-        p: Position { x: 0, y: 0 },
+    let (path, opts) = parse_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    });
+
+    let path = match path {
+        Some(path) => path,
+        None => return run::<Day11>(),
     };
 
-    let mut outputs = vec![];
-
-    loop {
-        let color = map.entry(current.p).or_insert(0);
+    let code = Day11::parse(&read_input!()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
 
-        match vm.step() {
-            Ok(Status::AwaitingInput) => vm.insert_input(*color),
-            Ok(Status::Output(o)) => outputs.push(o),
-            Ok(Status::Halted) => break,
-            Ok(_) => continue,
-            Err(e) => panic!("{:?}", e),
-        }
+    let hull = paint(&code, 1);
+    println!("{}", render(&hull));
 
-        if outputs.len() == 2 {
-            *color = outputs[0];
-            current.raw_turn(outputs[1]);
-            current.walk(1);
-            outputs.clear();
-        }
+    if let Err(e) = save(&hull, &path, &opts) {
+        eprintln!("unable to write {}: {}", path, e);
+        std::process::exit(1);
     }
 }
 
-fn display(map: &HashMap<Position, Int>) {
-    let mut max_u = std::i64::MIN;
-    let mut max_d = std::i64::MAX;
-    let mut max_l = std::i64::MAX;
-    let mut max_r = std::i64::MIN;
-
-    for p in map.keys() {
-        if p.x < max_l {
-            max_l = p.x
-        }
-        if p.x > max_r {
-            max_r = p.x
-        }
-        if p.y > max_u {
-            max_u = p.y
-        }
-        if p.y < max_d {
-            max_d = p.y
+fn parse_args() -> Option<(Option<String>, Options)> {
+    let mut path = None;
+    let mut opts = Options::default();
+    let mut styled = false;
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        styled |= arg != "--image";
+
+        match arg.as_ref() {
+            "--image" => path = Some(args.next()?),
+            "--scale" => opts.scale = args.next()?.parse().ok().filter(|s| *s > 0)?,
+            "--white" => opts.white = Rgb::parse(&args.next()?)?,
+            "--black" => opts.black = Rgb::parse(&args.next()?)?,
+            "--flip-y" => opts.flip_y = true,
+            _ => return None,
         }
     }
 
-    println!("d: {}, u: {}, l: {}, r: {}", max_d, max_u, max_l, max_r);
-    for y in (max_d..max_u + 1).rev() {
-        let mut row = String::new();
+    // the other options only affect the image
+    match &path {
+        Some(p) if Format::from_path(p).is_none() => None,
+        None if styled => None,
+        _ => Some((path, opts)),
+    }
+}
 
-        for x in max_l..max_r + 1 {
-            match map.get(&Position { x, y }) {
-                Some(1) => row.push('#'),
-                _ => row.push(' '),
-            }
-        }
+fn save(hull: &SparseGrid<Int>, path: &str, opts: &Options) -> std::io::Result<()> {
+    let format = Format::from_path(path).unwrap();
+    let image = Image::from_hull(hull, opts)
+        .ok_or_else(|| std::io::Error::other("the robot didn't paint anything"))?;
 
-        println!("{}", row);
-    }
+    image.write(BufWriter::new(File::create(path)?), format)
 }
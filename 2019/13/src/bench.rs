@@ -0,0 +1,67 @@
+use aoc2019_day13::controller::Controller;
+use aoc2019_day13::game::Game;
+use std::time::{Duration, Instant};
+use vm::{Error, Int};
+
+pub struct Report {
+    pub name: String,
+    pub score: u64,
+    pub blocks: usize,
+    pub frames: usize,
+    pub steps: usize,
+    pub elapsed: Duration,
+}
+
+// Plays a full game headless with each controller. Games are cut off after
+// `max_frames` joystick inputs so a controller that never loses can't hang
+// the run.
+pub fn run(
+    code: &[Int],
+    controllers: &mut [Box<dyn Controller>],
+    max_frames: usize,
+) -> Result<Vec<Report>, Error> {
+    controllers
+        .iter_mut()
+        .map(|c| {
+            let start = Instant::now();
+            let mut game = Game::new(code);
+            let mut frames = 0;
+
+            while game.next_frame()? && frames < max_frames {
+                let input = c.joystick(&game);
+                game.joystick(input);
+                frames += 1;
+            }
+
+            Ok(Report {
+                name: c.name().to_string(),
+                score: game.score,
+                blocks: game.blocks(),
+                frames,
+                steps: game.steps,
+                elapsed: start.elapsed(),
+            })
+        })
+        .collect()
+}
+
+pub fn table(reports: &[Report]) -> String {
+    let mut ret = format!(
+        "{:<12} {:>8} {:>8} {:>8} {:>10} {:>10}\n",
+        "strategy", "score", "blocks", "frames", "steps", "time"
+    );
+
+    for r in reports {
+        ret.push_str(&format!(
+            "{:<12} {:>8} {:>8} {:>8} {:>10} {:>8.1}ms\n",
+            r.name,
+            r.score,
+            r.blocks,
+            r.frames,
+            r.steps,
+            r.elapsed.as_secs_f64() * 1000.0
+        ));
+    }
+
+    ret
+}
@@ -0,0 +1,113 @@
+use crate::game::Game;
+use vm::Int;
+
+pub trait Controller {
+    fn name(&self) -> &str;
+
+    // Returns the joystick position (-1, 0 or 1) for the current frame.
+    fn joystick(&mut self, game: &Game) -> Int;
+}
+
+// Moves the paddle under the ball.
+pub struct Tracking;
+
+impl Controller for Tracking {
+    fn name(&self) -> &str {
+        "tracking"
+    }
+
+    fn joystick(&mut self, game: &Game) -> Int {
+        (game.ball.x - game.paddle.x).signum()
+    }
+}
+
+// Plays the frames ahead on a copy of the game, with the joystick held still,
+// to find where the ball will reach the paddle's row, and moves there early.
+// The ball doesn't touch the paddle before then, so the guess is exact.
+pub struct Predictive;
+
+impl Controller for Predictive {
+    fn name(&self) -> &str {
+        "predictive"
+    }
+
+    fn joystick(&mut self, game: &Game) -> Int {
+        let row = game.paddle.y - 1;
+        let mut ahead = game.clone();
+
+        while ahead.ball.y != row {
+            ahead.joystick(0);
+
+            // a game that ends or fails stops the ball where it is
+            if ahead.next_frame() != Ok(true) {
+                break;
+            }
+        }
+
+        (ahead.ball.x - game.paddle.x).signum()
+    }
+}
+
+// Mashes the joystick. Seeded, so runs are reproducible.
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed | 1 }
+    }
+}
+
+impl Controller for Random {
+    fn name(&self) -> &str {
+        "random"
+    }
+
+    fn joystick(&mut self, _: &Game) -> Int {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+
+        (self.state % 3) as Int - 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::game::Position;
+
+    // Draws the paddle at (2, 4) and then the ball at (0, 1), (1, 2) and
+    // (4, 3), asking for the joystick after each. The quarters patch turns the
+    // first instruction into a harmless multiply.
+    #[rustfmt::skip]
+    const SCRIPT: &[Int] = &[
+        1, 0, 0, 0,
+        104, 2, 104, 4, 104, 3,
+        104, 0, 104, 1, 104, 4, 3, 100,
+        104, 1, 104, 2, 104, 4, 3, 100,
+        104, 4, 104, 3, 104, 4, 3, 100,
+        99,
+    ];
+
+    #[test]
+    fn predictive_meets_the_ball() {
+        let mut game = Game::new(SCRIPT);
+        assert_eq!(game.next_frame(), Ok(true));
+
+        // the ball is to the left now, but reaches the paddle's row to its right
+        assert_eq!(Tracking.joystick(&game), -1);
+        assert_eq!(Predictive.joystick(&game), 1);
+        assert_eq!(game.ball, Position::new(0, 1));
+    }
+
+    #[test]
+    fn random_is_in_range() {
+        let mut c = Random::new(7);
+        let game = Game::new(&[99]);
+
+        assert!((0..100).all(|_| (-1..=1).contains(&c.joystick(&game))));
+    }
+}
@@ -0,0 +1,115 @@
+use crate::controller::Controller;
+use common::grid::SparseGrid;
+use common::point::Point2;
+use vm::{Error, Int, Status, Vm};
+
+pub type Position = Point2<i64>;
+
+// The arcade writes the score to this position instead of a tile.
+pub const SCORE: Position = Position { x: -1, y: 0 };
+
+#[derive(Clone)]
+pub struct Game {
+    vm: Vm,
+    pub display: SparseGrid<Sprite>,
+    pub score: u64,
+    pub ball: Position,
+    pub paddle: Position,
+    pub steps: usize,
+}
+
+impl Game {
+    pub fn new(code: &[Int]) -> Self {
+        Self {
+            // insert two quarters to play for free
+            vm: Vm::new(code).with_patches(&[(0, 2)]),
+            display: SparseGrid::new(),
+            score: 0,
+            ball: Position::default(),
+            paddle: Position::default(),
+            steps: 0,
+        }
+    }
+
+    // Runs the game until it wants joystick input (returning true) or halts.
+    // Fails if the program does, or draws a tile that isn't a `Sprite`.
+    pub fn next_frame(&mut self) -> Result<bool, Error> {
+        let mut out = vec![];
+
+        loop {
+            match self.vm.step()? {
+                Status::AwaitingInput => return Ok(true),
+                Status::Output(o) => out.push(o),
+                Status::Halted => return Ok(false),
+                Status::Running => (),
+            }
+
+            self.steps += 1;
+
+            if out.len() == 3 {
+                let p = Position {
+                    x: out[0],
+                    y: out[1],
+                };
+
+                if p == SCORE {
+                    self.score = out[2] as u64;
+                } else {
+                    let s = Sprite::from_raw(out[2]).ok_or(Error::InvalidOutput(out[2]))?;
+
+                    match s {
+                        Sprite::Ball => self.ball = p,
+                        Sprite::Paddle => self.paddle = p,
+                        _ => (),
+                    }
+
+                    self.display.insert(p, s);
+                }
+
+                out.clear();
+            }
+        }
+    }
+
+    pub fn joystick(&mut self, input: Int) {
+        self.vm.insert_input(input);
+    }
+
+    pub fn blocks(&self) -> usize {
+        self.display
+            .iter()
+            .filter(|(_, s)| **s == Sprite::Block)
+            .count()
+    }
+
+    pub fn simulate(&mut self, controller: &mut dyn Controller) -> Result<u64, Error> {
+        while self.next_frame()? {
+            let input = controller.joystick(self);
+            self.joystick(input);
+        }
+
+        Ok(self.score)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Sprite {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball,
+}
+
+impl Sprite {
+    pub fn from_raw(n: Int) -> Option<Self> {
+        match n {
+            0 => Some(Self::Empty),
+            1 => Some(Self::Wall),
+            2 => Some(Self::Block),
+            3 => Some(Self::Paddle),
+            4 => Some(Self::Ball),
+            _ => None,
+        }
+    }
+}
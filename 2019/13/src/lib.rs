@@ -0,0 +1,29 @@
+pub mod controller;
+pub mod game;
+
+use common::input::{self, InputError};
+use common::solution::Solution;
+use controller::Tracking;
+use game::Game;
+use std::fmt::Display;
+use vm::Int;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Int>;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError> {
+        input::parse(input, ',')
+    }
+
+    fn part1(code: &Self::Input) -> impl Display {
+        let mut game = Game::new(code);
+        game.next_frame().unwrap();
+        game.blocks()
+    }
+
+    fn part2(code: &Self::Input) -> impl Display {
+        Game::new(code).simulate(&mut Tracking).unwrap()
+    }
+}
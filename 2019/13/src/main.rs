@@ -1,119 +1,239 @@
+mod bench;
+mod replay;
+mod screen;
+mod terminal;
+
+use aoc2019_day13::controller::{Controller, Predictive, Random, Tracking};
+use aoc2019_day13::game::Game;
 use common::load_input;
-use std::collections::HashMap;
-use std::hash::Hash;
-use vm::{Int, Status, Vm};
-
-#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
-struct Position {
-    x: i64,
-    y: i64,
+use replay::Frame;
+use screen::{render, Screen};
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::time::Duration;
+use terminal::{Key, RawTerminal};
+use vm::Int;
+
+const USAGE: &str = "usage: day13 [--play | --autopilot] [--strategy <name>] [--delay <ms>]
+             [--record <file>]
+       day13 --bench
+       day13 --replay <file> [--delay <ms>]
+
+  --play       steer the paddle with the arrow keys (q quits)
+  --autopilot  watch a strategy play
+  --strategy   tracking (default), predictive or random
+  --record     save every frame with the joystick input that led to it
+  --bench      compare every strategy headless
+  --replay     play back a recorded game
+
+The input is the file named by AOC_INPUT, or else the input file next to the
+crate, and is read from stdin when AOC_INPUT is - or there's no such file.";
+
+// Frame limit for benchmarked games.
+const MAX_FRAMES: usize = 100_000;
+
+enum Mode {
+    Solve,
+    Play,
+    Autopilot,
+    Bench,
+    Replay(String),
 }
 
-struct Game {
-    vm: Vm,
-    display: HashMap<Position, Sprite>,
-    score: u64,
-    ball: Position,
-    paddle: Position,
+struct Options {
+    mode: Mode,
+    delay: Duration,
+    record: Option<String>,
+    strategy: Box<dyn Controller>,
 }
 
-impl Game {
-    pub fn new(code: &[Int]) -> Self {
-        let mut s = Self {
-            vm: Vm::new(code),
-            display: HashMap::new(),
-            score: 0,
-            ball: Position::default(),
-            paddle: Position::default(),
-        };
+fn main() {
+    let opts = parse_args().unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        std::process::exit(2);
+    });
+
+    if let Mode::Replay(path) = &opts.mode {
+        if let Err(e) = play_back(path, opts.delay) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
 
-        s.next_frame();
-        s.vm.reset();
-        s.vm.set(0, 2);
-        s
+        return;
     }
 
-    pub fn next_frame(&mut self) -> bool {
-        let mut out = vec![];
+    let code: Vec<Int> = load_input!(",", Int);
 
-        loop {
-            match self.vm.step() {
-                Ok(Status::AwaitingInput) => return true,
-                Ok(Status::Output(o)) => out.push(o),
-                Ok(Status::Halted) => return false,
-                Ok(_) => continue,
-                Err(e) => panic!("{:?}", e),
-            }
+    if let Mode::Bench = opts.mode {
+        match bench::run(&code, &mut strategies(), MAX_FRAMES) {
+            Ok(reports) => print!("{}", bench::table(&reports)),
+            Err(e) => crashed(e),
+        }
+
+        return;
+    }
 
-            if out.len() == 3 {
-                let p = Position {
-                    x: out[0],
-                    y: out[1],
-                };
+    let Options {
+        mode,
+        delay,
+        record,
+        mut strategy,
+    } = opts;
 
-                if p == (Position { x: -1, y: 0 }) {
-                    self.score = out[2] as u64;
-                } else {
-                    let s = Sprite::from_raw(out[2]);
+    let mut game = Game::new(&code);
 
-                    match s {
-                        Sprite::Ball => self.ball = p,
-                        Sprite::Paddle => self.paddle = p,
-                        _ => (),
-                    }
+    if let (Mode::Solve, None) = (&mode, &record) {
+        let score = game.next_frame().and_then(|_| {
+            println!("Block tiles: {}", game.blocks());
+            game.simulate(strategy.as_mut())
+        });
 
-                    self.display.insert(p, s);
-                }
+        match score {
+            Ok(score) => println!("Final score: {}", score),
+            Err(e) => crashed(e),
+        }
 
-                out.clear();
-            }
+        return;
+    }
+
+    let mut frames = vec![];
+    let autopilot = |g: &Game| Ok(Some(strategy.joystick(g)));
+
+    let result = match mode {
+        Mode::Play => play(&mut game, &mut frames),
+        Mode::Autopilot => drive(&mut game, Some(delay), &mut frames, autopilot),
+        _ => drive(&mut game, None, &mut frames, autopilot),
+    };
+
+    let result = result.and_then(|_| match &record {
+        Some(path) => replay::write(BufWriter::new(File::create(path)?), &frames),
+        None => Ok(()),
+    });
+
+    match result {
+        Ok(()) => {
+            println!("Final score: {}", game.score);
+            println!("Blocks remaining: {}", game.blocks());
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
     }
+}
 
-    pub fn simulate(&mut self) -> u64 {
-        while self.next_frame() {
-            self.vm.insert_input((self.ball.x - self.paddle.x).signum())
+fn parse_args() -> Option<Options> {
+    let mut opts = Options {
+        mode: Mode::Solve,
+        delay: Duration::from_millis(20),
+        record: None,
+        strategy: Box::new(Tracking),
+    };
+
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match (arg.as_ref(), &opts.mode) {
+            ("--play", Mode::Solve) => opts.mode = Mode::Play,
+            ("--autopilot", Mode::Solve) => opts.mode = Mode::Autopilot,
+            ("--bench", Mode::Solve) => opts.mode = Mode::Bench,
+            ("--replay", Mode::Solve) => opts.mode = Mode::Replay(args.next()?),
+            ("--strategy", _) => {
+                let name = args.next()?;
+                opts.strategy = strategies().into_iter().find(|s| s.name() == name)?;
+            }
+            ("--delay", _) => opts.delay = Duration::from_millis(args.next()?.parse().ok()?),
+            ("--record", _) => opts.record = Some(args.next()?),
+            _ => return None,
         }
+    }
 
-        self.score
+    match (&opts.mode, &opts.record) {
+        (Mode::Replay(_), Some(_)) => None,
+        _ => Some(opts),
     }
 }
 
-#[derive(Eq, PartialEq)]
-enum Sprite {
-    Empty,
-    Wall,
-    Block,
-    Paddle,
-    Ball,
+fn fault(e: vm::Error) -> String {
+    format!("the game crashed: {:?}", e)
+}
+
+fn crashed(e: vm::Error) -> ! {
+    eprintln!("{}", fault(e));
+    std::process::exit(1);
 }
 
-impl Sprite {
-    pub fn from_raw(n: Int) -> Self {
-        match n {
-            0 => Self::Empty,
-            1 => Self::Wall,
-            2 => Self::Block,
-            3 => Self::Paddle,
-            4 => Self::Ball,
-            n => panic!("unknown tile {}", n),
+fn strategies() -> Vec<Box<dyn Controller>> {
+    vec![
+        Box::new(Tracking),
+        Box::new(Predictive),
+        Box::new(Random::new(13)),
+    ]
+}
+
+// Runs the game, asking `joystick` for input every frame until the game ends
+// or it returns `None`. Frames are drawn when `delay` is set, and always
+// recorded into `frames`.
+fn drive<F>(
+    game: &mut Game,
+    delay: Option<Duration>,
+    frames: &mut Vec<Frame>,
+    mut joystick: F,
+) -> io::Result<()>
+where
+    F: FnMut(&Game) -> io::Result<Option<Int>>,
+{
+    let mut screen = Screen::new();
+    let mut input = None;
+
+    loop {
+        let running = game.next_frame().map_err(|e| io::Error::other(fault(e)))?;
+        frames.push(Frame::capture(game, frames.len(), input));
+
+        if let Some(delay) = delay {
+            screen.draw(&render(&game.display, game.score))?;
+            std::thread::sleep(delay);
+        }
+
+        if !running {
+            return Ok(());
         }
+
+        let i = match joystick(game)? {
+            Some(i) => i,
+            None => return Ok(()),
+        };
+
+        game.joystick(i);
+        input = Some(i);
     }
 }
 
-fn main() {
-    let code: Vec<Int> = load_input!(",", Int);
-    let mut game = Game::new(&code);
+fn play(game: &mut Game, frames: &mut Vec<Frame>) -> io::Result<()> {
+    let mut terminal = RawTerminal::new()?;
+
+    drive(game, Some(Duration::from_millis(0)), frames, |_| {
+        Ok(match terminal.read_key()? {
+            Key::Left => Some(-1),
+            Key::Right => Some(1),
+            Key::Quit => None,
+            Key::Other => Some(0),
+        })
+    })
+}
 
-    game.next_frame();
+fn play_back(path: &str, delay: Duration) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| format!("unable to open {}: {}", path, e))?;
+    let frames = replay::read(BufReader::new(file))?;
+    let mut screen = Screen::new();
 
-    let blocks: usize = game
-        .display
-        .iter()
-        .filter(|(_, v)| **v == Sprite::Block)
-        .collect::<HashMap<&Position, &Sprite>>()
-        .len();
+    for f in &frames {
+        screen
+            .draw(&format!("{}\n{}", f.status(), f.tiles))
+            .map_err(|e| e.to_string())?;
+
+        std::thread::sleep(delay);
+    }
 
-    println!("Block tiles: {}", blocks);
-    println!("Final score: {}", game.simulate());
+    Ok(())
 }
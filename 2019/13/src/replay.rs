@@ -0,0 +1,158 @@
+use crate::screen::render_tiles;
+use aoc2019_day13::game::{Game, Position};
+use std::io::{self, BufRead, Write};
+use vm::Int;
+
+// The game state right after `joystick` was applied (the first frame has no
+// input). Tiles are stored rendered, so a replay doesn't need the program.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub step: usize,
+    pub joystick: Option<Int>,
+    pub score: u64,
+    pub ball: Position,
+    pub paddle: Position,
+    pub tiles: String,
+}
+
+impl Frame {
+    pub fn capture(game: &Game, step: usize, joystick: Option<Int>) -> Self {
+        Self {
+            step,
+            joystick,
+            score: game.score,
+            ball: game.ball,
+            paddle: game.paddle,
+            tiles: render_tiles(&game.display),
+        }
+    }
+
+    pub fn status(&self) -> String {
+        let joystick = match self.joystick {
+            Some(j) => j.to_string(),
+            None => "-".to_string(),
+        };
+
+        format!(
+            "Step: {}  Joystick: {}  Score: {}",
+            self.step, joystick, self.score
+        )
+    }
+}
+
+// Text format, one header line per frame followed by its tile rows:
+//
+//   frame <step> joystick <-1|0|1|-> score <n> ball <x>,<y> paddle <x>,<y> rows <n>
+pub fn write<W: Write>(mut w: W, frames: &[Frame]) -> io::Result<()> {
+    for f in frames {
+        let joystick = f.joystick.map_or("-".to_string(), |j| j.to_string());
+
+        writeln!(
+            w,
+            "frame {} joystick {} score {} ball {},{} paddle {},{} rows {}",
+            f.step,
+            joystick,
+            f.score,
+            f.ball.x,
+            f.ball.y,
+            f.paddle.x,
+            f.paddle.y,
+            f.tiles.lines().count()
+        )?;
+
+        write!(w, "{}", f.tiles)?;
+    }
+
+    Ok(())
+}
+
+pub fn read<R: BufRead>(r: R) -> Result<Vec<Frame>, String> {
+    let mut lines = r.lines().enumerate();
+    let mut frames = vec![];
+
+    while let Some((n, header)) = lines.next() {
+        let header = header.map_err(|e| e.to_string())?;
+        let err = |what: &str| format!("line {}: {}: {:?}", n + 1, what, header);
+
+        let fields: Vec<&str> = header.split_whitespace().collect();
+        let (step, joystick, score, ball, paddle, rows) = match fields[..] {
+            ["frame", step, "joystick", joystick, "score", score, "ball", ball, "paddle", paddle, "rows", rows] => {
+                (step, joystick, score, ball, paddle, rows)
+            }
+            _ => return Err(err("invalid frame header")),
+        };
+
+        let position = |s: &str| -> Option<Position> {
+            let (x, y) = s.split_at(s.find(',')?);
+            Some(Position {
+                x: x.parse().ok()?,
+                y: y[1..].parse().ok()?,
+            })
+        };
+
+        let rows: usize = rows.parse().map_err(|_| err("invalid row count"))?;
+        let mut tiles = String::new();
+
+        for _ in 0..rows {
+            match lines.next() {
+                Some((_, Ok(row))) => {
+                    tiles.push_str(&row);
+                    tiles.push('\n');
+                }
+                _ => return Err(err("frame is missing rows")),
+            }
+        }
+
+        frames.push(Frame {
+            step: step.parse().map_err(|_| err("invalid step"))?,
+            joystick: match joystick {
+                "-" => None,
+                j => Some(j.parse().map_err(|_| err("invalid joystick"))?),
+            },
+            score: score.parse().map_err(|_| err("invalid score"))?,
+            ball: position(ball).ok_or_else(|| err("invalid ball position"))?,
+            paddle: position(paddle).ok_or_else(|| err("invalid paddle position"))?,
+            tiles,
+        });
+    }
+
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let frames = vec![
+            Frame {
+                step: 0,
+                joystick: None,
+                score: 0,
+                ball: Position { x: 2, y: 1 },
+                paddle: Position { x: 2, y: 2 },
+                tiles: "#####\n# o #\n  -  \n".to_string(),
+            },
+            Frame {
+                step: 1,
+                joystick: Some(-1),
+                score: 12,
+                ball: Position { x: 1, y: 1 },
+                paddle: Position { x: 1, y: 2 },
+                tiles: "#####\n#o  #\n -   \n".to_string(),
+            },
+        ];
+
+        let mut buf = vec![];
+        write(&mut buf, &frames).unwrap();
+
+        assert_eq!(read(&buf[..]), Ok(frames));
+    }
+
+    #[test]
+    fn truncated() {
+        let text = "frame 0 joystick - score 0 ball 0,0 paddle 0,0 rows 3\n###\n";
+        assert!(read(text.as_bytes()).is_err());
+    }
+}
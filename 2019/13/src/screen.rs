@@ -0,0 +1,81 @@
+use aoc2019_day13::game::Sprite;
+use common::grid::SparseGrid;
+use std::io::{self, Write};
+
+pub trait Tile {
+    fn glyph(&self) -> char;
+}
+
+impl Tile for Sprite {
+    fn glyph(&self) -> char {
+        match self {
+            Sprite::Empty => ' ',
+            Sprite::Wall => '#',
+            Sprite::Block => '=',
+            Sprite::Paddle => '-',
+            Sprite::Ball => 'o',
+        }
+    }
+}
+
+// Renders a tile map with the score segment above it.
+pub fn render<T: Tile>(tiles: &SparseGrid<T>, score: u64) -> String {
+    format!("Score: {}\n{}", score, render_tiles(tiles))
+}
+
+// Positions missing from `tiles` are drawn as blanks. Every row ends in a
+// newline.
+pub fn render_tiles<T: Tile>(tiles: &SparseGrid<T>) -> String {
+    let mut ret = tiles.render(|t| t.map_or(' ', Tile::glyph));
+
+    if !ret.is_empty() {
+        ret.push('\n');
+    }
+
+    ret
+}
+
+// Redraws frames in place using ANSI escape codes.
+pub struct Screen {
+    cleared: bool,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self { cleared: false }
+    }
+
+    pub fn draw(&mut self, frame: &str) -> io::Result<()> {
+        let mut out = io::stdout();
+
+        if !self.cleared {
+            write!(out, "\x1b[2J")?;
+            self.cleared = true;
+        }
+
+        // raw mode doesn't translate \n, so return the cursor explicitly
+        write!(out, "\x1b[H{}", frame.replace('\n', "\x1b[K\r\n"))?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::point::Point2;
+
+    #[test]
+    fn render_frame() {
+        let mut tiles = SparseGrid::new();
+
+        for x in 0..5 {
+            tiles.insert(Point2::new(x, 0), Sprite::Wall);
+        }
+
+        tiles.insert(Point2::new(1, 1), Sprite::Block);
+        tiles.insert(Point2::new(3, 1), Sprite::Ball);
+        tiles.insert(Point2::new(2, 2), Sprite::Paddle);
+
+        assert_eq!(render(&tiles, 42), "Score: 42\n#####\n = o \n  -  \n");
+    }
+}
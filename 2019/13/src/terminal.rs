@@ -0,0 +1,119 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Key {
+    Left,
+    Right,
+    Quit,
+    Other,
+}
+
+// Puts the controlling terminal into raw mode for as long as it's alive, so
+// single key presses can be read without waiting for enter. Stdin is left
+// alone since it carries the puzzle input.
+pub struct RawTerminal {
+    tty: File,
+    saved: String,
+    // bytes read but not yet parsed into keys
+    pending: Vec<u8>,
+}
+
+impl RawTerminal {
+    pub fn new() -> io::Result<Self> {
+        let tty = File::open("/dev/tty")?;
+        let saved = stty(&["-g"])?;
+
+        // from here on dropping `terminal` restores the saved settings, so an
+        // error can't leave the terminal raw
+        let terminal = Self {
+            tty,
+            saved: saved.trim().to_string(),
+            pending: vec![],
+        };
+
+        stty(&["raw", "-echo"])?;
+        Ok(terminal)
+    }
+
+    // Waits for the next key press. An escape sequence can arrive split over
+    // several reads, or several keys in one, so bytes are kept until they
+    // make up a whole key.
+    pub fn read_key(&mut self) -> io::Result<Key> {
+        loop {
+            if let Some((key, len)) = parse_key(&self.pending) {
+                self.pending.drain(..len);
+                return Ok(key);
+            }
+
+            let mut buf = [0; 16];
+            match self.tty.read(&mut buf)? {
+                0 => return Ok(Key::Quit),
+                n => self.pending.extend_from_slice(&buf[..n]),
+            }
+        }
+    }
+}
+
+// The first key in `bytes` and how many bytes it takes, or `None` if they
+// stop partway through one.
+fn parse_key(bytes: &[u8]) -> Option<(Key, usize)> {
+    match bytes {
+        [] | [b'\x1b'] | [b'\x1b', b'['] => None,
+        [b'\x1b', b'[', b'D', ..] => Some((Key::Left, 3)),
+        [b'\x1b', b'[', b'C', ..] => Some((Key::Right, 3)),
+        [b'\x1b', b'[', _, ..] => Some((Key::Other, 3)),
+        [b'a' | b'h', ..] => Some((Key::Left, 1)),
+        [b'd' | b'l', ..] => Some((Key::Right, 1)),
+        [b'q' | b'\x03', ..] => Some((Key::Quit, 1)),
+        _ => Some((Key::Other, 1)),
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let out = Command::new("stty")
+        .args(args)
+        .stdin(File::open("/dev/tty")?)
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !out.status.success() {
+        return Err(io::Error::other("stty failed"));
+    }
+
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn keys(mut bytes: &[u8]) -> Vec<Key> {
+        let mut ret = vec![];
+
+        while let Some((key, len)) = parse_key(bytes) {
+            ret.push(key);
+            bytes = &bytes[len..];
+        }
+
+        ret
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(keys(b"aa"), [Key::Left, Key::Left]);
+        assert_eq!(keys(b"\x1b[Dd"), [Key::Left, Key::Right]);
+        assert_eq!(keys(b"x\x1b[Aq"), [Key::Other, Key::Other, Key::Quit]);
+
+        // the rest of the sequence hasn't arrived yet
+        assert_eq!(keys(b"l\x1b"), [Key::Right]);
+        assert_eq!(parse_key(b"\x1b["), None);
+    }
+}
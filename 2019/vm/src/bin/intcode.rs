@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use vm::{Int, Status, Vm};
+
+const USAGE: &str = "usage: intcode [--ascii] [--optimize] <program>
+
+Runs an Intcode program, prompting for input whenever it asks for some.
+While prompted, `:ascii` toggles ASCII mode and `:quit` exits.";
+
+fn main() {
+    let mut ascii = false;
+    let mut optimize = false;
+    let mut path = None;
+
+    for arg in std::env::args().skip(1) {
+        match arg.as_ref() {
+            "--ascii" => ascii = true,
+            "--optimize" => optimize = true,
+            "-h" | "--help" => exit(USAGE, 0),
+            _ if path.is_none() => path = Some(arg),
+            _ => exit(USAGE, 2),
+        }
+    }
+
+    let path = path.unwrap_or_else(|| exit(USAGE, 2));
+    let source = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| exit(&format!("unable to read {}: {}", path, e), 1));
+
+    let code: Vec<Int> = source
+        .trim()
+        .split(',')
+        .enumerate()
+        .map(|(i, n)| {
+            n.trim()
+                .parse()
+                .unwrap_or_else(|_| exit(&format!("invalid value {:?} at position {}", n, i), 1))
+        })
+        .collect();
+
+    let mut vm = Vm::new(&code);
+    if optimize {
+        vm = vm.optimized();
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut pending: VecDeque<Int> = VecDeque::new();
+    let mut steps: u64 = 0;
+
+    loop {
+        let status = vm.step();
+
+        // count retired instructions, not stalls or faults
+        if let Ok(Status::Running | Status::Output(_) | Status::Halted) = status {
+            steps += 1;
+        }
+
+        match status {
+            Ok(Status::Running) => (),
+            Ok(Status::Output(o)) => match ascii && (0..128).contains(&o) {
+                true => print!("{}", o as u8 as char),
+                false => println!("{}", o),
+            },
+            Ok(Status::AwaitingInput) => loop {
+                if let Some(i) = pending.pop_front() {
+                    vm.insert_input(i);
+                    break;
+                }
+
+                print!("{}", if ascii { "ascii> " } else { "> " });
+                io::stdout().flush().unwrap();
+
+                let line = match lines.next() {
+                    Some(Ok(line)) => line,
+                    _ => exit(&format!("\nend of input after {} steps", steps), 1),
+                };
+
+                match line.trim() {
+                    ":ascii" => {
+                        ascii = !ascii;
+                        println!("ascii mode {}", if ascii { "on" } else { "off" });
+                    }
+                    ":quit" => exit(&format!("quit after {} steps", steps), 0),
+                    _ if ascii => {
+                        pending.extend(line.bytes().map(Int::from));
+                        pending.push_back('\n' as Int);
+                    }
+                    n => match n.parse() {
+                        Ok(i) => pending.push_back(i),
+                        Err(_) => println!("not a number: {:?}", n),
+                    },
+                }
+            },
+            Ok(Status::Halted) => exit(&format!("halted after {} steps", steps), 0),
+            Err(e) => exit(&format!("error after {} steps: {:?}", steps, e), 1),
+        }
+
+        io::stdout().flush().unwrap();
+    }
+}
+
+fn exit(message: &str, code: i32) -> ! {
+    if code == 0 {
+        println!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+
+    std::process::exit(code)
+}
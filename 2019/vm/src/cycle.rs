@@ -0,0 +1,93 @@
+use crate::error::Error;
+use crate::status::Status;
+use crate::vm::Vm;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub first: usize,
+    pub repeat: usize,
+}
+
+impl Cycle {
+    pub fn period(&self) -> usize {
+        self.repeat - self.first
+    }
+}
+
+// Steps `vm` until it halts or revisits a state it has already been in. The
+// driver is handed every status other than `Running` so it can feed input or
+// consume output. Step 0 is the state before the first step. States are
+// compared by `Vm::state_hash`, so anything the driver keeps outside of the
+// vm is not taken into account.
+pub fn find_cycle<F>(vm: &mut Vm, mut driver: F) -> Result<Option<Cycle>, Error>
+where
+    F: FnMut(&mut Vm, Status),
+{
+    let mut seen = HashMap::new();
+    seen.insert(vm.state_hash(), 0);
+
+    let mut step = 0;
+
+    loop {
+        let status = vm.step()?;
+        step += 1;
+
+        match status {
+            Status::Halted => return Ok(None),
+            Status::Running => (),
+            s => driver(vm, s),
+        }
+
+        if let Some(first) = seen.insert(vm.state_hash(), step) {
+            return Ok(Some(Cycle {
+                first,
+                repeat: step,
+            }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn halting_program_has_no_cycle() {
+        let mut vm = Vm::new(&[1101, 1, 2, 5, 99, 0]);
+        assert_eq!(find_cycle(&mut vm, |_, _| ()).unwrap(), None);
+    }
+
+    #[test]
+    fn jump_to_start() {
+        // add 1 + 1 into [7], then unconditionally jump back to 0
+        let mut vm = Vm::new(&[1101, 1, 1, 7, 1105, 1, 0, 0]);
+
+        assert_eq!(
+            find_cycle(&mut vm, |_, _| ()).unwrap(),
+            Some(Cycle {
+                first: 1,
+                repeat: 3
+            })
+        );
+    }
+
+    #[test]
+    fn driver_input_is_part_of_state() {
+        // echo input forever
+        let code = [3, 9, 4, 9, 1105, 1, 0, 0, 0, 0];
+
+        let mut vm = Vm::new(&code);
+        let mut n = 0;
+        let cycle = find_cycle(&mut vm, |vm, s| {
+            if let Status::AwaitingInput = s {
+                n += 1;
+                vm.insert_input(n % 3);
+            }
+        })
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(cycle.period(), 12);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::Int;
+use std::collections::VecDeque;
+
+// A peripheral mapped into a range of vm memory. Offsets are relative to the
+// start of the mapped range.
+//
+// An instruction can still stall or fault after reading its operands, so
+// reads don't change the device. `nth` counts the earlier reads of `offset`
+// by the same instruction, and once it retires the vm calls `commit` with
+// how many there were.
+pub trait Device {
+    fn read(&self, offset: usize, nth: usize) -> Int;
+    fn write(&mut self, offset: usize, value: Int);
+
+    fn commit(&mut self, _offset: usize, _reads: usize) {}
+
+    // Called once after every instruction that retires.
+    fn tick(&mut self) {}
+}
+
+// Writes are appended to `output`, reads pop from `input` (0 when empty).
+#[derive(Clone, Debug, Default)]
+pub struct Console {
+    pub input: VecDeque<Int>,
+    pub output: Vec<Int>,
+}
+
+impl Console {
+    pub fn new(input: &[Int]) -> Self {
+        Self {
+            input: input.iter().cloned().collect(),
+            output: vec![],
+        }
+    }
+}
+
+impl Device for Console {
+    fn read(&self, _: usize, nth: usize) -> Int {
+        self.input.get(nth).cloned().unwrap_or(0)
+    }
+
+    fn write(&mut self, _: usize, value: Int) {
+        self.output.push(value);
+    }
+
+    fn commit(&mut self, _: usize, reads: usize) {
+        self.input.drain(..reads.min(self.input.len()));
+    }
+}
+
+// Counts retired instructions. Writing sets the counter.
+#[derive(Clone, Debug, Default)]
+pub struct Timer {
+    pub ticks: Int,
+}
+
+impl Device for Timer {
+    fn read(&self, _: usize, _: usize) -> Int {
+        self.ticks
+    }
+
+    fn write(&mut self, _: usize, value: Int) {
+        self.ticks = value;
+    }
+
+    fn tick(&mut self) {
+        self.ticks += 1;
+    }
+}
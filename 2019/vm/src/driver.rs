@@ -0,0 +1,244 @@
+use crate::error::Error;
+use crate::status::Status;
+use crate::vm::Vm;
+use crate::Int;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::poll_fn;
+use std::rc::Rc;
+use std::task::{Poll, Waker};
+
+// A single-threaded, unbounded channel of Intcode values. The receiving side
+// wakes its task whenever a value is sent or the last sender is dropped.
+pub fn channel() -> (Sender, Receiver) {
+    let shared = Rc::new(RefCell::new(Shared {
+        queue: VecDeque::new(),
+        waker: None,
+        senders: 1,
+    }));
+
+    (Sender(shared.clone()), Receiver(shared))
+}
+
+struct Shared {
+    queue: VecDeque<Int>,
+    waker: Option<Waker>,
+    senders: usize,
+}
+
+impl Shared {
+    fn wake(&mut self) {
+        if let Some(w) = self.waker.take() {
+            w.wake();
+        }
+    }
+}
+
+pub struct Sender(Rc<RefCell<Shared>>);
+
+impl Sender {
+    pub fn send(&self, value: Int) {
+        let mut shared = self.0.borrow_mut();
+        shared.queue.push_back(value);
+        shared.wake();
+    }
+}
+
+impl Clone for Sender {
+    fn clone(&self) -> Self {
+        self.0.borrow_mut().senders += 1;
+        Sender(self.0.clone())
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        let mut shared = self.0.borrow_mut();
+        shared.senders -= 1;
+        shared.wake();
+    }
+}
+
+pub struct Receiver(Rc<RefCell<Shared>>);
+
+impl Receiver {
+    // Resolves to the next value, or `None` once every sender is gone and the
+    // queue is drained.
+    pub async fn recv(&mut self) -> Option<Int> {
+        poll_fn(|cx| {
+            let mut shared = self.0.borrow_mut();
+
+            match shared.queue.pop_front() {
+                Some(v) => Poll::Ready(Some(v)),
+                None if shared.senders == 0 => Poll::Ready(None),
+                None => {
+                    shared.waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    pub fn try_recv(&mut self) -> Option<Int> {
+        self.0.borrow_mut().queue.pop_front()
+    }
+}
+
+// Drives a `Vm` from async code, pulling input from a channel whenever the
+// program asks for it. Doesn't depend on any particular runtime.
+pub struct AsyncVm {
+    vm: Vm,
+    input: Receiver,
+}
+
+impl AsyncVm {
+    pub fn new(vm: Vm, input: Receiver) -> Self {
+        Self { vm, input }
+    }
+
+    // Resolves to the next output, or `None` once the program halts.
+    pub async fn next_output(&mut self) -> Result<Option<Int>, Error> {
+        if self.vm.halted() {
+            return Ok(None);
+        }
+
+        loop {
+            match self.vm.step()? {
+                Status::Output(o) => return Ok(Some(o)),
+                Status::Halted => return Ok(None),
+                Status::AwaitingInput => match self.input.recv().await {
+                    Some(i) => self.vm.insert_input(i),
+                    None => return Err(Error::InputClosed),
+                },
+                Status::Running => (),
+            }
+        }
+    }
+
+    // Runs the program to completion, forwarding every output to `output`.
+    // Resolves once the program halts.
+    pub async fn run(mut self, output: Sender) -> Result<Self, Error> {
+        while let Some(o) = self.next_output().await? {
+            output.send(o);
+        }
+
+        Ok(self)
+    }
+
+    pub fn into_inner(self) -> (Vm, Receiver) {
+        (self.vm, self.input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Wake};
+
+    type Task<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+    struct Woken(AtomicBool);
+
+    impl Wake for Woken {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    // Polls every task round-robin until they have all completed.
+    fn join_all<'a, T>(mut tasks: Vec<Task<'a, T>>) -> Vec<T> {
+        let woken = Arc::new(Woken(AtomicBool::new(false)));
+        let waker = Waker::from(woken.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut done: Vec<Option<T>> = tasks.iter().map(|_| None).collect();
+
+        while done.iter().any(|d| d.is_none()) {
+            let mut progress = false;
+
+            for (task, done) in tasks.iter_mut().zip(done.iter_mut()) {
+                if done.is_none() {
+                    if let Poll::Ready(v) = task.as_mut().poll(&mut cx) {
+                        *done = Some(v);
+                        progress = true;
+                    }
+                }
+            }
+
+            progress |= woken.0.swap(false, Ordering::SeqCst);
+            assert!(progress || done.iter().all(|d| d.is_some()), "deadlock");
+        }
+
+        done.into_iter().map(|d| d.unwrap()).collect()
+    }
+
+    fn block_on<T>(f: impl Future<Output = T>) -> T {
+        join_all(vec![Box::pin(f)]).pop().unwrap()
+    }
+
+    #[test]
+    fn next_output() {
+        // outputs input * 2 until the input is 0
+        let code = [
+            3, 15, 1006, 15, 14, 1002, 15, 2, 15, 4, 15, 1105, 1, 0, 99, 0,
+        ];
+        let (tx, rx) = channel();
+        let mut vm = AsyncVm::new(Vm::new(&code), rx);
+
+        tx.send(3);
+        tx.send(5);
+        tx.send(0);
+
+        block_on(async {
+            assert_eq!(vm.next_output().await, Ok(Some(6)));
+            assert_eq!(vm.next_output().await, Ok(Some(10)));
+            assert_eq!(vm.next_output().await, Ok(None));
+            assert_eq!(vm.next_output().await, Ok(None));
+        });
+    }
+
+    #[test]
+    fn closed_input() {
+        let (tx, rx) = channel();
+        let mut vm = AsyncVm::new(Vm::new(&[3, 0, 99]), rx);
+        drop(tx);
+
+        assert_eq!(block_on(vm.next_output()), Err(Error::InputClosed));
+    }
+
+    #[test]
+    fn amplifier_feedback_loop() {
+        let code = [
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let phases = [9, 8, 7, 6, 5];
+
+        let (senders, receivers): (Vec<_>, Vec<_>) = phases.iter().map(|_| channel()).unzip();
+
+        for (tx, p) in senders.iter().zip(phases.iter()) {
+            tx.send(*p);
+        }
+        senders[0].send(0);
+
+        let tasks: Vec<Task<Result<AsyncVm, Error>>> = receivers
+            .into_iter()
+            .enumerate()
+            .map(|(i, rx)| {
+                let output = senders[(i + 1) % phases.len()].clone();
+                Box::pin(AsyncVm::new(Vm::new(&code), rx).run(output)) as Task<_>
+            })
+            .collect();
+
+        drop(senders);
+
+        let mut amps = join_all(tasks);
+        let (_, mut rx) = amps.remove(0).unwrap().into_inner();
+
+        assert_eq!(rx.try_recv(), Some(139629729));
+    }
+}
@@ -1,4 +1,17 @@
-#[derive(Debug)]
+use crate::Int;
+use std::ops::Range;
+
+#[derive(Debug, PartialEq)]
 pub enum Error {
     AlreadyHalted,
+    DeviceOverlap(Range<usize>),
+    InvalidOpcode(Int),
+    InvalidMode(Int),
+    InvalidAddress(Int),
+    ImmediateWrite,
+    InputClosed,
+    NoOutput,
+    DiagnosticFailed(usize, Int),
+    Overflow,
+    InvalidOutput(Int),
 }
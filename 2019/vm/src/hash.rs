@@ -0,0 +1,25 @@
+use crate::Int;
+
+// splitmix64 finalizer
+pub fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+// Zero cells hash to zero so the amount of padding behind the program doesn't
+// affect the result.
+pub fn cell(offset: usize, value: Int) -> u64 {
+    if value == 0 {
+        return 0;
+    }
+
+    mix(mix(offset as u64) ^ value as u64)
+}
+
+pub fn memory(mem: &[Int]) -> u64 {
+    mem.iter()
+        .enumerate()
+        .fold(0, |acc, (i, v)| acc ^ cell(i, *v))
+}
@@ -0,0 +1,141 @@
+use crate::error::Error;
+use crate::op::{Intention, Mode, Op};
+use crate::Int;
+
+// An instruction with its parameters already fetched. `Pos` and `Rel` hold
+// the raw parameter, which is resolved against memory / the relative base
+// when the instruction executes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Operand {
+    Imm(Int),
+    Pos(Int),
+    Rel(Int),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cmp {
+    Less,
+    Equal,
+}
+
+impl Cmp {
+    pub fn eval(self, a: Int, b: Int) -> Int {
+        let r = match self {
+            Cmp::Less => a < b,
+            Cmp::Equal => a == b,
+        };
+
+        r as Int
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instr {
+    Add(Operand, Operand, Operand),
+    Mul(Operand, Operand, Operand),
+    Input(Operand),
+    Output(Operand),
+    Jump {
+        cond: Operand,
+        target: Operand,
+        when: bool,
+    },
+    Compare(Cmp, Operand, Operand, Operand),
+    AdjustBase(Operand),
+    Halt,
+
+    // produced by the optimizer
+    Store(Operand, Int),
+    Copy(Operand, Operand),
+    CompareJump {
+        cmp: Cmp,
+        a: Operand,
+        b: Operand,
+        dst: Operand,
+        target: Operand,
+        when: bool,
+    },
+}
+
+impl Instr {
+    // Decodes the instruction at `pc`, returning it with the number of cells
+    // it occupies.
+    pub fn decode<F>(pc: usize, fetch: F) -> Result<(Self, usize), Error>
+    where
+        F: Fn(usize) -> Int,
+    {
+        let p = |n: usize, i: Intention| -> Result<Operand, Error> {
+            let raw = fetch(pc + n);
+
+            match i {
+                Intention::Read(Mode::Immediate) => Ok(Operand::Imm(raw)),
+                Intention::Write(Mode::Immediate) => Err(Error::ImmediateWrite),
+                Intention::Read(Mode::Position) | Intention::Write(Mode::Position) => {
+                    Ok(Operand::Pos(raw))
+                }
+                Intention::Read(Mode::Relative) | Intention::Write(Mode::Relative) => {
+                    Ok(Operand::Rel(raw))
+                }
+            }
+        };
+
+        Ok(match Op::from_raw(fetch(pc))? {
+            Op::Add(a, b, c) => (Instr::Add(p(1, a)?, p(2, b)?, p(3, c)?), 4),
+            Op::Mul(a, b, c) => (Instr::Mul(p(1, a)?, p(2, b)?, p(3, c)?), 4),
+            Op::Input(a) => (Instr::Input(p(1, a)?), 2),
+            Op::Output(a) => (Instr::Output(p(1, a)?), 2),
+            Op::JumpIfTrue(a, b) => {
+                let (cond, target) = (p(1, a)?, p(2, b)?);
+                (
+                    Instr::Jump {
+                        cond,
+                        target,
+                        when: true,
+                    },
+                    3,
+                )
+            }
+            Op::JumpIfFalse(a, b) => {
+                let (cond, target) = (p(1, a)?, p(2, b)?);
+                (
+                    Instr::Jump {
+                        cond,
+                        target,
+                        when: false,
+                    },
+                    3,
+                )
+            }
+            Op::LessThan(a, b, c) => (Instr::Compare(Cmp::Less, p(1, a)?, p(2, b)?, p(3, c)?), 4),
+            Op::Equal(a, b, c) => (Instr::Compare(Cmp::Equal, p(1, a)?, p(2, b)?, p(3, c)?), 4),
+            Op::AdjustBase(a) => (Instr::AdjustBase(p(1, a)?), 2),
+            Op::Halt => (Instr::Halt, 1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decode() {
+        let code = [21107, 4, -3, 7, 99];
+        let fetch = |i: usize| code.get(i).cloned().unwrap_or(0);
+
+        assert_eq!(
+            Instr::decode(0, fetch),
+            Ok((
+                Instr::Compare(
+                    Cmp::Less,
+                    Operand::Imm(4),
+                    Operand::Imm(-3),
+                    Operand::Rel(7)
+                ),
+                4
+            ))
+        );
+        assert_eq!(Instr::decode(4, fetch), Ok((Instr::Halt, 1)));
+        assert_eq!(Instr::decode(0, |_| 11103), Err(Error::ImmediateWrite));
+    }
+}
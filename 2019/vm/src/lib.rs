@@ -1,9 +1,23 @@
+mod cycle;
+mod device;
+mod driver;
 mod error;
+mod hash;
+mod instr;
 mod op;
+mod optimize;
+pub mod robot;
+mod run;
+mod search;
 mod status;
 mod vm;
 
+pub use crate::cycle::{find_cycle, Cycle};
+pub use crate::device::{Console, Device, Timer};
+pub use crate::driver::{channel, AsyncVm, Receiver, Sender};
 pub use crate::error::Error;
+pub use crate::run::RunResult;
+pub use crate::search::{search, Strategy};
 pub use crate::status::Status;
 pub use crate::vm::Vm;
 
@@ -1,3 +1,4 @@
+use crate::error::Error;
 use crate::Int;
 
 #[derive(Debug, PartialEq)]
@@ -14,12 +15,12 @@ pub enum Mode {
 }
 
 impl Mode {
-    pub fn from_raw(n: Int) -> Self {
+    pub fn from_raw(n: Int) -> Result<Self, Error> {
         match n {
-            0 => Mode::Position,
-            1 => Mode::Immediate,
-            2 => Mode::Relative,
-            _ => panic!("invalid mode: {}", n),
+            0 => Ok(Mode::Position),
+            1 => Ok(Mode::Immediate),
+            2 => Ok(Mode::Relative),
+            _ => Err(Error::InvalidMode(n)),
         }
     }
 }
@@ -51,14 +52,14 @@ macro_rules! w {
 }
 
 impl Op {
-    pub fn from_raw(n: Int) -> Self {
+    pub fn from_raw(n: Int) -> Result<Self, Error> {
         let raw = n % 100;
 
-        let a = Mode::from_raw((n / 100) % 10);
-        let b = Mode::from_raw((n / 1000) % 10);
-        let c = Mode::from_raw((n / 10000) % 10);
+        let a = Mode::from_raw((n / 100) % 10)?;
+        let b = Mode::from_raw((n / 1000) % 10)?;
+        let c = Mode::from_raw((n / 10000) % 10)?;
 
-        match raw {
+        Ok(match raw {
             1 => Op::Add(r!(a), r!(b), w!(c)),
             2 => Op::Mul(r!(a), r!(b), w!(c)),
             3 => Op::Input(w!(a)),
@@ -69,8 +70,8 @@ impl Op {
             8 => Op::Equal(r!(a), r!(b), w!(c)),
             9 => Op::AdjustBase(r!(a)),
             99 => Op::Halt,
-            _ => panic!("unknown opcode: {}", raw),
-        }
+            _ => return Err(Error::InvalidOpcode(n)),
+        })
     }
 }
 
@@ -81,7 +82,7 @@ mod test {
     #[test]
     fn test() {
         assert_eq!(
-            Op::from_raw(1),
+            Op::from_raw(1).unwrap(),
             Op::Add(
                 Intention::Read(Mode::Position),
                 Intention::Read(Mode::Position),
@@ -90,7 +91,7 @@ mod test {
         );
 
         assert_eq!(
-            Op::from_raw(102),
+            Op::from_raw(102).unwrap(),
             Op::Mul(
                 Intention::Read(Mode::Immediate),
                 Intention::Read(Mode::Position),
@@ -99,7 +100,7 @@ mod test {
         );
 
         assert_eq!(
-            Op::from_raw(1002),
+            Op::from_raw(1002).unwrap(),
             Op::Mul(
                 Intention::Read(Mode::Position),
                 Intention::Read(Mode::Immediate),
@@ -108,7 +109,7 @@ mod test {
         );
 
         assert_eq!(
-            Op::from_raw(1102),
+            Op::from_raw(1102).unwrap(),
             Op::Mul(
                 Intention::Read(Mode::Immediate),
                 Intention::Read(Mode::Immediate),
@@ -116,4 +117,12 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Op::from_raw(0), Err(Error::InvalidOpcode(0)));
+        assert_eq!(Op::from_raw(-1), Err(Error::InvalidOpcode(-1)));
+        assert_eq!(Op::from_raw(301), Err(Error::InvalidMode(3)));
+        assert_eq!(Op::from_raw(-201), Err(Error::InvalidMode(-2)));
+    }
 }
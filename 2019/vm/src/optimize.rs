@@ -0,0 +1,149 @@
+use crate::instr::{Instr, Operand};
+
+// Longest instruction the optimizer can produce (a fused compare + jump).
+const MAX_LEN: usize = 7;
+
+// Rewrites a decoded instruction into a cheaper equivalent. `next` is the
+// instruction directly following it, which is folded in for compare-and-branch
+// pairs where the jump tests the value the compare just wrote.
+pub fn peephole(instr: Instr, len: usize, next: Option<(Instr, usize)>) -> (Instr, usize) {
+    use Operand::Imm;
+
+    let folded = match instr {
+        Instr::Add(Imm(a), Imm(b), dst) => a.checked_add(b).map(|v| Instr::Store(dst, v)),
+        Instr::Mul(Imm(a), Imm(b), dst) => a.checked_mul(b).map(|v| Instr::Store(dst, v)),
+        Instr::Add(x, Imm(0), dst) | Instr::Add(Imm(0), x, dst) => Some(Instr::Copy(x, dst)),
+        Instr::Mul(x, Imm(1), dst) | Instr::Mul(Imm(1), x, dst) => Some(Instr::Copy(x, dst)),
+        Instr::Compare(cmp, a, b, dst) => match next {
+            Some((Instr::Jump { cond, target, when }, n)) if cond == dst => {
+                return (
+                    Instr::CompareJump {
+                        cmp,
+                        a,
+                        b,
+                        dst,
+                        target,
+                        when,
+                    },
+                    len + n,
+                );
+            }
+            _ => None,
+        },
+        _ => None,
+    };
+
+    (folded.unwrap_or(instr), len)
+}
+
+// Optimized instructions indexed by the address they were decoded from.
+// Entries are dropped as soon as any cell they cover is written, so
+// self-modifying code falls back to decoding the new contents.
+#[derive(Clone, Default)]
+pub struct Cache {
+    entries: Vec<Option<(Instr, usize)>>,
+}
+
+impl Cache {
+    pub fn get(&self, pc: usize) -> Option<(Instr, usize)> {
+        self.entries.get(pc).cloned().flatten()
+    }
+
+    pub fn insert(&mut self, pc: usize, entry: (Instr, usize)) {
+        if pc >= self.entries.len() {
+            self.entries.resize(pc + 1, None);
+        }
+
+        self.entries[pc] = Some(entry);
+    }
+
+    pub fn invalidate(&mut self, offset: usize) {
+        let end = (offset + 1).min(self.entries.len());
+
+        for pc in offset.saturating_sub(MAX_LEN - 1)..end {
+            if let Some((_, len)) = self.entries[pc] {
+                if pc + len > offset {
+                    self.entries[pc] = None;
+                }
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::instr::Cmp;
+    use Operand::{Imm, Pos, Rel};
+
+    #[test]
+    fn constant_folding() {
+        assert_eq!(
+            peephole(Instr::Add(Imm(2), Imm(3), Pos(9)), 4, None),
+            (Instr::Store(Pos(9), 5), 4)
+        );
+        assert_eq!(
+            peephole(Instr::Mul(Imm(2), Imm(3), Rel(1)), 4, None),
+            (Instr::Store(Rel(1), 6), 4)
+        );
+        assert_eq!(
+            peephole(Instr::Add(Imm(0), Pos(4), Pos(9)), 4, None),
+            (Instr::Copy(Pos(4), Pos(9)), 4)
+        );
+        assert_eq!(
+            peephole(Instr::Mul(Rel(4), Imm(1), Pos(9)), 4, None),
+            (Instr::Copy(Rel(4), Pos(9)), 4)
+        );
+
+        // overflow is left for the vm to report
+        let overflow = Instr::Mul(Imm(i64::MAX), Imm(2), Pos(0));
+        assert_eq!(peephole(overflow, 4, None), (overflow, 4));
+    }
+
+    #[test]
+    fn compare_jump_fusion() {
+        let cmp = Instr::Compare(Cmp::Equal, Pos(1), Imm(8), Pos(20));
+        let jump = |cond| Instr::Jump {
+            cond,
+            target: Imm(0),
+            when: true,
+        };
+
+        assert_eq!(
+            peephole(cmp, 4, Some((jump(Pos(20)), 3))),
+            (
+                Instr::CompareJump {
+                    cmp: Cmp::Equal,
+                    a: Pos(1),
+                    b: Imm(8),
+                    dst: Pos(20),
+                    target: Imm(0),
+                    when: true,
+                },
+                7
+            )
+        );
+
+        assert_eq!(peephole(cmp, 4, Some((jump(Pos(21)), 3))), (cmp, 4));
+        assert_eq!(peephole(cmp, 4, None), (cmp, 4));
+    }
+
+    #[test]
+    fn invalidate() {
+        let mut cache = Cache::default();
+        cache.insert(0, (Instr::Halt, 7));
+        cache.insert(7, (Instr::Halt, 1));
+
+        cache.invalidate(8);
+        assert!(cache.get(0).is_some());
+        assert!(cache.get(7).is_some());
+
+        cache.invalidate(6);
+        assert!(cache.get(0).is_none());
+        assert!(cache.get(7).is_some());
+    }
+}
@@ -0,0 +1,166 @@
+use crate::error::Error;
+use crate::status::Status;
+use crate::vm::Vm;
+use crate::Int;
+use common::grid::SparseGrid;
+use common::point::{Direction, Point2};
+
+// What a batch of program output asks the robot to do. Actions are applied in
+// order.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action<C> {
+    Set(C),
+    TurnLeft,
+    TurnRight,
+    Face(Direction),
+    Forward(i64),
+}
+
+impl<C> Action<C> {
+    // The usual encoding: 0 turns left, 1 turns right.
+    pub fn turn(n: Int) -> Result<Self, Error> {
+        match n {
+            0 => Ok(Action::TurnLeft),
+            1 => Ok(Action::TurnRight),
+            n => Err(Error::InvalidOutput(n)),
+        }
+    }
+}
+
+type Sensor<C> = Box<dyn Fn(Point2<i64>, &C) -> Int>;
+type Decoder<C> = Box<dyn Fn(&[Int]) -> Result<Vec<Action<C>>, Error>>;
+
+// An Intcode program driving a robot around an unbounded grid. Whenever the
+// program wants input it's given the sensor reading for the robot's cell, and
+// every `arity` outputs are decoded into actions. Cells that were never set
+// read as `empty`, and only cells that were set end up in `grid`.
+pub struct Robot<C> {
+    vm: Vm,
+    pub position: Point2<i64>,
+    pub heading: Direction,
+    pub grid: SparseGrid<C>,
+    empty: C,
+    sensor: Sensor<C>,
+    arity: usize,
+    decoder: Decoder<C>,
+}
+
+impl<C: Clone + 'static> Robot<C> {
+    // A robot at the origin facing up, which reads nothing and ignores all
+    // output until `sensor` and `protocol` are configured.
+    pub fn new(vm: Vm, empty: C) -> Self {
+        Self {
+            vm,
+            position: Point2::default(),
+            heading: Direction::Up,
+            grid: SparseGrid::new(),
+            empty,
+            sensor: Box::new(|_, _| 0),
+            arity: 1,
+            decoder: Box::new(|_| Ok(vec![])),
+        }
+    }
+
+    pub fn sensor<F>(mut self, sensor: F) -> Self
+    where
+        F: Fn(Point2<i64>, &C) -> Int + 'static,
+    {
+        self.sensor = Box::new(sensor);
+        self
+    }
+
+    pub fn protocol<F>(mut self, arity: usize, decoder: F) -> Self
+    where
+        F: Fn(&[Int]) -> Result<Vec<Action<C>>, Error> + 'static,
+    {
+        assert!(arity > 0, "a protocol needs at least one output");
+
+        self.arity = arity;
+        self.decoder = Box::new(decoder);
+        self
+    }
+
+    pub fn get(&self, p: Point2<i64>) -> &C {
+        self.grid.get(p).unwrap_or(&self.empty)
+    }
+
+    pub fn apply(&mut self, action: Action<C>) {
+        match action {
+            Action::Set(c) => {
+                self.grid.insert(self.position, c);
+            }
+            Action::TurnLeft => self.heading = self.heading.turn_left(),
+            Action::TurnRight => self.heading = self.heading.turn_right(),
+            Action::Face(h) => self.heading = h,
+            Action::Forward(n) => self.position += self.heading.offset() * n,
+        }
+    }
+
+    // Runs the program until it halts. Output left over when it halts, fewer
+    // than `arity` values, is discarded.
+    pub fn run(&mut self) -> Result<(), Error> {
+        let mut outputs = vec![];
+
+        loop {
+            match self.vm.step()? {
+                Status::AwaitingInput => {
+                    let reading = (self.sensor)(self.position, self.get(self.position));
+                    self.vm.insert_input(reading);
+                }
+                Status::Output(o) => outputs.push(o),
+                Status::Halted => return Ok(()),
+                Status::Running => (),
+            }
+
+            if outputs.len() == self.arity {
+                for action in (self.decoder)(&outputs)? {
+                    self.apply(action);
+                }
+
+                outputs.clear();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn painter() {
+        // reads the current color, then outputs its inverse and turns right
+        // until it has painted four panels
+        let code = [
+            3, 100, 1002, 100, -1, 100, 1001, 100, 1, 100, 4, 100, 104, 1, 1001, 101, 1, 101, 1007,
+            101, 4, 102, 1005, 102, 0, 99,
+        ];
+
+        let mut robot = Robot::new(Vm::new(&code), 0)
+            .sensor(|_, c| *c)
+            .protocol(2, |out| {
+                Ok(vec![
+                    Action::Set(out[0]),
+                    Action::turn(out[1])?,
+                    Action::Forward(1),
+                ])
+            });
+
+        robot.run().unwrap();
+
+        // four right turns walk a square back to the origin
+        assert_eq!(robot.position, Point2::default());
+        assert_eq!(robot.heading, Direction::Up);
+        assert_eq!(robot.grid.len(), 4);
+        assert!(robot.grid.iter().all(|(_, c)| *c == 1));
+    }
+
+    #[test]
+    fn invalid_output() {
+        let mut robot = Robot::new(Vm::new(&[104, 1, 104, 7, 99]), 0).protocol(2, |out| {
+            Ok(vec![Action::Set(out[0]), Action::turn(out[1])?])
+        });
+
+        assert_eq!(robot.run(), Err(Error::InvalidOutput(7)));
+    }
+}
@@ -0,0 +1,53 @@
+use crate::error::Error;
+use crate::status::Status;
+use crate::Int;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunResult {
+    pub outputs: Vec<Int>,
+    pub steps: usize,
+    pub status: Status,
+    pub inputs_consumed: usize,
+}
+
+impl RunResult {
+    pub fn last_output(&self) -> Option<Int> {
+        self.outputs.last().cloned()
+    }
+
+    // Diagnostic programs (2019 day 5) output 0 for every test that passes,
+    // followed by the diagnostic code.
+    pub fn diagnostic(&self) -> Result<Int, Error> {
+        let (code, tests) = self.outputs.split_last().ok_or(Error::NoOutput)?;
+
+        match tests.iter().position(|o| *o != 0) {
+            Some(i) => Err(Error::DiagnosticFailed(i, tests[i])),
+            None => Ok(*code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn result(outputs: &[Int]) -> RunResult {
+        RunResult {
+            outputs: outputs.to_vec(),
+            steps: 0,
+            status: Status::Halted,
+            inputs_consumed: 0,
+        }
+    }
+
+    #[test]
+    fn diagnostic() {
+        assert_eq!(result(&[0, 0, 0, 42]).diagnostic(), Ok(42));
+        assert_eq!(result(&[42]).diagnostic(), Ok(42));
+        assert_eq!(
+            result(&[0, 3, 0, 42]).diagnostic(),
+            Err(Error::DiagnosticFailed(1, 3))
+        );
+        assert_eq!(result(&[]).diagnostic(), Err(Error::NoOutput));
+    }
+}
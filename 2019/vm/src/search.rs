@@ -0,0 +1,118 @@
+use crate::error::Error;
+use crate::status::Status;
+use crate::vm::Vm;
+use crate::Int;
+use std::collections::{HashSet, VecDeque};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    BreadthFirst,
+    DepthFirst,
+}
+
+// Explores every sequence of `inputs` fed to `start`, one value per
+// `AwaitingInput`, until the outputs produced in response to the last input
+// satisfy `goal`. Returns the input sequence that got there, or `None` once
+// every reachable state has been visited. States are deduplicated with
+// `Vm::state_hash`, so programs that loop back to an earlier state (e.g. a
+// droid walking back to a visited cell) terminate. Only `BreadthFirst` is
+// guaranteed to return the shortest sequence.
+pub fn search<G>(
+    start: &Vm,
+    inputs: &[Int],
+    goal: G,
+    strategy: Strategy,
+) -> Result<Option<Vec<Int>>, Error>
+where
+    G: Fn(&[Int]) -> bool,
+{
+    let mut vm = start.clone();
+
+    if goal(&advance(&mut vm)?) {
+        return Ok(Some(vec![]));
+    }
+
+    let mut seen = HashSet::new();
+    let mut frontier = VecDeque::new();
+
+    if !vm.halted() {
+        seen.insert(vm.state_hash());
+        frontier.push_back((vm, vec![]));
+    }
+
+    loop {
+        let next = match strategy {
+            Strategy::BreadthFirst => frontier.pop_front(),
+            Strategy::DepthFirst => frontier.pop_back(),
+        };
+
+        let (vm, path) = match next {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+
+        for i in inputs {
+            let mut next = vm.clone();
+            next.insert_input(*i);
+
+            let outputs = advance(&mut next)?;
+
+            let mut path = path.clone();
+            path.push(*i);
+
+            if goal(&outputs) {
+                return Ok(Some(path));
+            }
+
+            if !next.halted() && seen.insert(next.state_hash()) {
+                frontier.push_back((next, path));
+            }
+        }
+    }
+}
+
+// Runs the vm until it halts or needs more input, returning its outputs.
+fn advance(vm: &mut Vm) -> Result<Vec<Int>, Error> {
+    let mut outputs = vec![];
+
+    loop {
+        match vm.step()? {
+            Status::Output(o) => outputs.push(o),
+            Status::AwaitingInput | Status::Halted => return Ok(outputs),
+            Status::Running => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // adds each input to an accumulator and outputs the running total
+    const ACCUMULATOR: [Int; 11] = [3, 100, 1, 100, 101, 101, 4, 101, 1105, 1, 0];
+
+    #[test]
+    fn breadth_first_finds_shortest() {
+        let vm = Vm::new(&ACCUMULATOR);
+        let path = search(&vm, &[1, 2], |o| o == [5], Strategy::BreadthFirst).unwrap();
+
+        assert_eq!(path, Some(vec![1, 2, 2]));
+    }
+
+    #[test]
+    fn depth_first() {
+        let vm = Vm::new(&ACCUMULATOR);
+        let path = search(&vm, &[1, 2], |o| o == [5], Strategy::DepthFirst).unwrap();
+
+        assert_eq!(path, Some(vec![2, 2, 1]));
+    }
+
+    #[test]
+    fn exhausted() {
+        // outputs its input once, then halts
+        let vm = Vm::new(&[3, 5, 4, 5, 99, 0]);
+        let path = search(&vm, &[1, 2], |o| o == [3], Strategy::BreadthFirst).unwrap();
+
+        assert_eq!(path, None);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::Int;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Status {
     Running,
     Halted,
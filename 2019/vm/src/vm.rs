@@ -1,40 +1,123 @@
+use crate::device::Device;
 use crate::error::Error;
-use crate::op::{Intention, Mode, Op};
+use crate::hash;
+use crate::instr::{Instr, Operand};
+use crate::optimize::{self, Cache};
+use crate::run::RunResult;
 use crate::status::Status;
 use crate::Int;
+use std::cell::RefCell;
+use std::ops::Range;
+use std::rc::Rc;
+
+// Upper bound on addressable memory, so a stray write can't allocate
+// gigabytes.
+const MEMORY_LIMIT: usize = 1 << 20;
+
+type Mapping = (Range<usize>, Rc<RefCell<dyn Device>>);
 
 #[derive(Clone)]
 pub struct Vm {
     pc: usize,
-    bp: usize,
+    bp: Int,
     mem: Vec<Int>,
     input: Option<Int>,
     output: Option<Int>,
     code: Vec<Int>,
     status: Status,
+    hash: u64,
+    devices: Vec<Mapping>,
+    // Device addresses read by the instruction in flight, committed to
+    // their devices only once it retires.
+    reads: RefCell<Vec<usize>>,
+    cache: Option<Cache>,
 }
 
 impl Vm {
     pub fn new(code: &[Int]) -> Self {
-        let mut mem = code.to_vec();
-        mem.extend(vec![0; 1000]);
+        let mem = code.to_vec();
 
         Self {
             pc: 0,
             bp: 0,
+            hash: hash::memory(&mem),
             mem,
             input: None,
             output: None,
             code: code.to_vec(),
             status: Status::Running,
+            devices: vec![],
+            reads: RefCell::default(),
+            cache: None,
+        }
+    }
+
+    // Writes `patches` (address, value) over the program, so they are applied
+    // again by every `reset`.
+    pub fn with_patches(mut self, patches: &[(usize, Int)]) -> Self {
+        for (offset, value) in patches {
+            if *offset >= self.code.len() {
+                self.code.resize(offset + 1, 0);
+            }
+
+            self.code[*offset] = *value;
         }
+
+        self.reset();
+        self
+    }
+
+    // Caches decoded instructions and runs them through the peephole
+    // optimizer. Fused instructions retire in a single `step`, so step counts
+    // (and anything keyed on them, like `find_cycle`) differ from an
+    // unoptimized vm, but memory, I/O and the final state don't.
+    pub fn optimized(mut self) -> Self {
+        self.cache = Some(Cache::default());
+        self
     }
 
     pub fn halted(&self) -> bool {
-        match self.status {
-            Status::Halted => true,
-            _ => false,
+        matches!(self.status, Status::Halted)
+    }
+
+    // Hash of everything that determines how the vm will evolve: pc, bp,
+    // memory and the pending input / status. The memory part is maintained
+    // incrementally by `set`, so this is cheap to call after every step.
+    pub fn state_hash(&self) -> u64 {
+        let input = self.input.map_or(0, |i| hash::mix(i as u64) | 1);
+        let status = match self.status {
+            Status::Running => 0,
+            Status::Halted => 1,
+            Status::AwaitingInput => 2,
+            Status::Output(o) => hash::mix(o as u64) | 3,
+        };
+
+        [self.pc as u64, self.bp as u64, input, status]
+            .iter()
+            .fold(self.hash, |acc, v| hash::mix(acc ^ v))
+    }
+
+    // Routes reads and writes of `range` to `device` instead of memory. The
+    // returned handle can be used to inspect the device while the vm runs.
+    // Cloned vms share their devices, and device state isn't part of
+    // `state_hash`.
+    pub fn map_device<D>(&mut self, range: Range<usize>, device: D) -> Result<Rc<RefCell<D>>, Error>
+    where
+        D: Device + 'static,
+    {
+        let overlaps = self
+            .devices
+            .iter()
+            .any(|(r, _)| range.start < r.end && r.start < range.end);
+
+        if overlaps {
+            return Err(Error::DeviceOverlap(range));
         }
+
+        let device = Rc::new(RefCell::new(device));
+        self.devices.push((range, device.clone()));
+
+        Ok(device)
     }
 
     pub fn insert_input(&mut self, input: Int) {
@@ -42,156 +125,432 @@ impl Vm {
     }
 
     pub fn step(&mut self) -> Result<Status, Error> {
-        match self.status {
+        let before = self.status;
+        match before {
             Status::Halted => return Err(Error::AlreadyHalted),
             Status::Output(_) => self.status = Status::Running,
             _ => (),
         }
 
-        let op = Op::from_raw(self.get(self.pc));
-        self.pc += 1;
+        self.reads.get_mut().clear();
+
+        // Faulting leaves the vm and its devices as they were, and stalling on
+        // input leaves the devices too.
+        let status = match self.execute() {
+            Ok(status) => status,
+            Err(e) => {
+                self.status = before;
+                return Err(e);
+            }
+        };
+
+        if status != Status::AwaitingInput {
+            self.commit();
+        }
+
+        Ok(status)
+    }
+
+    fn execute(&mut self) -> Result<Status, Error> {
+        let (instr, len) = self.fetch()?;
+        let next = self.pc + len;
 
-        match op {
-            Op::Add(ma, mb, mc) => {
-                let (a, b, c) = self.params(self.pc, ma, mb, mc);
-                self.set(c, a + b);
-                self.pc += 3;
+        match instr {
+            Instr::Add(a, b, c) => {
+                let v = self.read(a)?.checked_add(self.read(b)?);
+                self.write(self.dest(c)?, v.ok_or(Error::Overflow)?)?;
+                self.pc = next;
             }
-            Op::Mul(ma, mb, mc) => {
-                let (a, b, c) = self.params(self.pc, ma, mb, mc);
-                self.set(c, a * b);
-                self.pc += 3;
+            Instr::Mul(a, b, c) => {
+                let v = self.read(a)?.checked_mul(self.read(b)?);
+                self.write(self.dest(c)?, v.ok_or(Error::Overflow)?)?;
+                self.pc = next;
             }
-            Op::Input(ma) => match self.input {
+            Instr::Input(a) => match self.input {
                 Some(i) => {
-                    let a = self.param(self.pc, ma);
-                    self.set(a, i);
+                    self.write(self.dest(a)?, i)?;
                     self.input = None;
                     self.status = Status::Running;
-                    self.pc += 1;
-                }
-                None => {
-                    self.status = Status::AwaitingInput;
-                    self.pc -= 1;
+                    self.pc = next;
                 }
+                None => self.status = Status::AwaitingInput,
             },
-            Op::Output(ma) => {
-                self.output = Some(self.param(self.pc, ma));
+            Instr::Output(a) => {
+                self.output = Some(self.read(a)?);
                 self.status = Status::Output(self.output.unwrap());
-                self.pc += 1;
+                self.pc = next;
             }
-            Op::JumpIfTrue(ma, mb) => {
-                let a = self.param(self.pc, ma);
-                let b = self.param(self.pc + 1, mb);
-
-                self.pc += 2;
+            Instr::Jump { cond, target, when } => {
+                let c = self.read(cond)?;
+                let t = self.read(target)?;
 
-                if a != 0 {
-                    self.pc = b as usize;
-                }
+                self.pc = if (c != 0) == when { address(t)? } else { next };
             }
-            Op::JumpIfFalse(ma, mb) => {
-                let a = self.param(self.pc, ma);
-                let b = self.param(self.pc + 1, mb);
-
-                self.pc += 2;
-
-                if a == 0 {
-                    self.pc = b as usize;
-                }
+            Instr::Compare(cmp, a, b, c) => {
+                let v = cmp.eval(self.read(a)?, self.read(b)?);
+                self.write(self.dest(c)?, v)?;
+                self.pc = next;
             }
-            Op::LessThan(ma, mb, mc) => {
-                let (a, b, c) = self.params(self.pc, ma, mb, mc);
+            Instr::AdjustBase(a) => {
+                let a = self.read(a)?;
+                self.bp = self.bp.checked_add(a).ok_or(Error::Overflow)?;
+                self.pc = next;
+            }
+            Instr::Halt => self.status = Status::Halted,
+            Instr::Store(dst, v) => {
+                self.write(self.dest(dst)?, v)?;
+                self.pc = next;
+            }
+            Instr::Copy(src, dst) => {
+                self.write(self.dest(dst)?, self.read(src)?)?;
+                self.pc = next;
+            }
+            Instr::CompareJump {
+                cmp,
+                a,
+                b,
+                dst,
+                target,
+                when,
+            } => {
+                let v = cmp.eval(self.read(a)?, self.read(b)?);
+                let d = self.dest(dst)?;
 
-                if a < b {
-                    self.set(c, 1);
-                } else {
-                    self.set(c, 0);
-                }
+                // The target is read before the compare writes, so account for
+                // it pointing at the compare's destination.
+                let t = match self.dest(target) {
+                    Ok(t) if t == d => Ok(v),
+                    _ => self.read(target),
+                };
+                let jump = t.and_then(|t| match (v != 0) == when {
+                    true => address(t),
+                    false => Ok(next),
+                });
 
-                self.pc += 3;
-            }
-            Op::Equal(ma, mb, mc) => {
-                let (a, b, c) = self.params(self.pc, ma, mb, mc);
+                self.write(d, v)?;
 
-                if a == b {
-                    self.set(c, 1);
-                } else {
-                    self.set(c, 0);
+                // If the jump half would fault or was just overwritten, only
+                // retire the compare and let the next step decode the jump.
+                match jump {
+                    Ok(pc) if !(self.pc as Int..next as Int).contains(&d) => self.pc = pc,
+                    _ => self.pc += 4,
                 }
-
-                self.pc += 3;
             }
-            Op::AdjustBase(ma) => {
-                let a = self.param(self.pc, ma);
-                self.bp = ((self.bp as Int) + a) as usize;
-                self.pc += 1;
-            }
-            Op::Halt => self.status = Status::Halted,
         }
 
         Ok(self.status)
     }
 
-    pub fn run(&mut self, mut inputs: Vec<Int>) -> Option<Int> {
+    pub fn run_from_start(&mut self, inputs: &[Int]) -> Result<RunResult, Error> {
         self.reset();
+        self.resume(inputs)
+    }
+
+    // Continues from the current state until the program halts or asks for
+    // more input than `inputs` holds. Anything primed with `insert_input` or
+    // `set` beforehand is kept.
+    pub fn resume(&mut self, inputs: &[Int]) -> Result<RunResult, Error> {
+        let mut inputs = inputs.iter();
+        let mut result = RunResult {
+            outputs: vec![],
+            steps: 0,
+            status: Status::Running,
+            inputs_consumed: 0,
+        };
 
         loop {
-            match self.step() {
-                Ok(Status::AwaitingInput) => self.input = Some(inputs.remove(0)),
-                Ok(Status::Halted) => break,
-                Ok(_) => continue,
-                Err(e) => panic!("Error: {:?}", e),
+            result.status = self.step()?;
+
+            match result.status {
+                Status::AwaitingInput => match inputs.next() {
+                    Some(i) => {
+                        self.insert_input(*i);
+                        result.inputs_consumed += 1;
+                        continue;
+                    }
+                    None => break,
+                },
+                Status::Output(o) => result.outputs.push(o),
+                Status::Halted => {
+                    result.steps += 1;
+                    break;
+                }
+                Status::Running => (),
             }
+
+            result.steps += 1;
         }
 
-        self.output
+        Ok(result)
     }
 
+    // Memory past the end of the program reads as zero. Looking at a device
+    // this way doesn't affect it.
     pub fn get(&self, offset: usize) -> Int {
-        self.mem[offset]
+        match self.device(offset) {
+            Some((start, d)) => d.borrow().read(offset - start, 0),
+            None => self.mem.get(offset).cloned().unwrap_or(0),
+        }
     }
 
     pub fn set(&mut self, offset: Int, value: Int) {
-        self.mem[offset as usize] = value;
+        if let Err(e) = self.write(offset, value) {
+            panic!("unable to set {}: {:?}", offset, e);
+        }
     }
 
     pub fn reset(&mut self) {
-        let mut mem = self.code.to_vec();
-        mem.extend(vec![0; 10000]);
+        let mem = self.code.to_vec();
 
         self.pc = 0;
+        self.bp = 0;
+        self.hash = hash::memory(&mem);
         self.mem = mem;
         self.input = None;
         self.output = None;
         self.status = Status::Running;
+
+        if let Some(cache) = &mut self.cache {
+            cache.clear();
+        }
     }
 
-    fn params(&self, begin: usize, ma: Intention, mb: Intention, mc: Intention) -> (Int, Int, Int) {
-        (
-            self.param(begin + 0, ma),
-            self.param(begin + 1, mb),
-            self.param(begin + 2, mc),
-        )
+    fn write(&mut self, offset: Int, value: Int) -> Result<(), Error> {
+        let offset = address(offset)?;
+
+        if let Some((start, d)) = self.device(offset) {
+            d.borrow_mut().write(offset - start, value);
+            return Ok(());
+        }
+
+        if offset >= self.mem.len() {
+            self.mem.resize(offset + 1, 0);
+        }
+
+        self.hash ^= hash::cell(offset, self.mem[offset]) ^ hash::cell(offset, value);
+        self.mem[offset] = value;
+
+        if let Some(cache) = &mut self.cache {
+            cache.invalidate(offset);
+        }
+
+        Ok(())
     }
 
-    fn param(&self, p: usize, i: Intention) -> Int {
-        match i {
-            Intention::Read(m) => match m {
-                Mode::Position => self.get(self.get(p) as usize),
-                Mode::Immediate => self.get(p),
-                Mode::Relative => self.get((self.bp as Int + self.get(p)) as usize),
-            },
-            Intention::Write(m) => match m {
-                Mode::Position => self.get(p),
-                Mode::Immediate => panic!("can't write to an immediate"),
-                Mode::Relative => self.bp as Int + self.get(p),
-            },
+    // A read by the instruction in flight, which devices see on `commit`.
+    fn load(&self, offset: usize) -> Int {
+        let (start, d) = match self.device(offset) {
+            Some(mapping) => mapping,
+            None => return self.get(offset),
+        };
+
+        let mut reads = self.reads.borrow_mut();
+        let nth = reads.iter().filter(|r| **r == offset).count();
+        reads.push(offset);
+
+        d.borrow().read(offset - start, nth)
+    }
+
+    // Hands the retired instruction's reads to their devices, then ticks
+    // them all.
+    fn commit(&mut self) {
+        let mut reads = std::mem::take(self.reads.get_mut());
+        reads.sort_unstable();
+
+        for same in reads.chunk_by(|a, b| a == b) {
+            if let Some((start, d)) = self.device(same[0]) {
+                d.borrow_mut().commit(same[0] - start, same.len());
+            }
+        }
+
+        for (_, d) in &self.devices {
+            d.borrow_mut().tick();
+        }
+    }
+
+    fn device(&self, offset: usize) -> Option<(usize, &RefCell<dyn Device>)> {
+        self.devices
+            .iter()
+            .find(|(r, _)| r.contains(&offset))
+            .map(|(r, d)| (r.start, d.as_ref()))
+    }
+
+    fn fetch(&mut self) -> Result<(Instr, usize), Error> {
+        // devices may change what a read returns, so don't cache around them
+        if self.cache.is_none() || !self.devices.is_empty() {
+            return Instr::decode(self.pc, |i| self.load(i));
+        }
+
+        if let Some(entry) = self.cache.as_ref().and_then(|c| c.get(self.pc)) {
+            return Ok(entry);
+        }
+
+        let (instr, len) = Instr::decode(self.pc, |i| self.get(i))?;
+        let next = Instr::decode(self.pc + len, |i| self.get(i)).ok();
+        let entry = optimize::peephole(instr, len, next);
+
+        if let Some(cache) = &mut self.cache {
+            cache.insert(self.pc, entry);
+        }
+
+        Ok(entry)
+    }
+
+    fn read(&self, o: Operand) -> Result<Int, Error> {
+        match o {
+            Operand::Imm(v) => Ok(v),
+            _ => Ok(self.load(address(self.dest(o)?)?)),
+        }
+    }
+
+    fn dest(&self, o: Operand) -> Result<Int, Error> {
+        match o {
+            Operand::Imm(_) => Err(Error::ImmediateWrite),
+            Operand::Pos(p) => Ok(p),
+            Operand::Rel(r) => self.bp.checked_add(r).ok_or(Error::Overflow),
         }
     }
 }
 
-// #[cfg(test)]
-// mod test {
-//     use super::*;
-// }
+fn address(n: Int) -> Result<usize, Error> {
+    if n < 0 || n >= MEMORY_LIMIT as Int {
+        return Err(Error::InvalidAddress(n));
+    }
+
+    Ok(n as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::device::{Console, Timer};
+
+    // echo inputs until a 0 is read
+    const ECHO: [Int; 10] = [3, 9, 4, 9, 1005, 9, 0, 99, 0, 0];
+
+    #[test]
+    fn run_from_start() {
+        let mut vm = Vm::new(&ECHO);
+
+        assert_eq!(
+            vm.run_from_start(&[4, 2, 0]),
+            Ok(RunResult {
+                outputs: vec![4, 2, 0],
+                steps: 10,
+                status: Status::Halted,
+                inputs_consumed: 3,
+            })
+        );
+
+        assert_eq!(
+            vm.run_from_start(&[4]),
+            Ok(RunResult {
+                outputs: vec![4],
+                steps: 3,
+                status: Status::AwaitingInput,
+                inputs_consumed: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn resume() {
+        let mut vm = Vm::new(&ECHO);
+        vm.insert_input(7);
+
+        let first = vm.resume(&[3]).unwrap();
+        assert_eq!(first.outputs, vec![7, 3]);
+        assert_eq!(first.status, Status::AwaitingInput);
+
+        let second = vm.resume(&[0]).unwrap();
+        assert_eq!(second.outputs, vec![0]);
+        assert_eq!(second.status, Status::Halted);
+
+        assert_eq!(vm.resume(&[]), Err(Error::AlreadyHalted));
+    }
+
+    #[test]
+    fn patches_survive_reset() {
+        // output [5]
+        let mut vm = Vm::new(&[4, 5, 99, 0, 0, 1]).with_patches(&[(5, 2), (8, 3)]);
+
+        assert_eq!(vm.get(5), 2);
+        assert_eq!(vm.get(8), 3);
+        assert_eq!(vm.run_from_start(&[]).unwrap().outputs, vec![2]);
+
+        vm.set(5, 9);
+        assert_eq!(vm.run_from_start(&[]).unwrap().outputs, vec![2]);
+    }
+
+    #[test]
+    fn console_device() {
+        // [500] = [500] + [500], twice
+        let mut vm = Vm::new(&[1, 500, 500, 500, 1, 500, 500, 500, 99]);
+        let console = vm
+            .map_device(500..501, Console::new(&[1, 2, 3, 4]))
+            .unwrap();
+
+        while !vm.halted() {
+            vm.step().unwrap();
+        }
+
+        assert_eq!(console.borrow().output, vec![3, 7]);
+    }
+
+    #[test]
+    fn timer_device() {
+        // [0] = 0 + 0, [0] = [600] * 1, then output [0]
+        let mut vm = Vm::new(&[1101, 0, 0, 0, 1002, 600, 1, 0, 4, 0, 99]);
+        vm.map_device(600..601, Timer::default()).unwrap();
+
+        let mut out = None;
+        while !vm.halted() {
+            if let Status::Output(o) = vm.step().unwrap() {
+                out = Some(o);
+            }
+        }
+
+        assert_eq!(out, Some(1));
+    }
+
+    #[test]
+    fn faults_keep_state() {
+        let mut vm = Vm::new(&[104, 7, 98]);
+        assert_eq!(vm.step().unwrap(), Status::Output(7));
+
+        let hash = vm.state_hash();
+        assert!(vm.step().is_err());
+        assert_eq!(vm.state_hash(), hash);
+    }
+
+    #[test]
+    fn unretired_reads() {
+        // [-1] = [500] + [500] faults after reading the console
+        let mut vm = Vm::new(&[1, 500, 500, -1]);
+        let console = vm.map_device(500..501, Console::new(&[1, 2])).unwrap();
+        let timer = vm.map_device(600..601, Timer::default()).unwrap();
+
+        assert!(vm.step().is_err());
+        assert_eq!(console.borrow().input, [1, 2]);
+        assert_eq!(timer.borrow().ticks, 0);
+
+        // waiting for input doesn't tick
+        let mut vm = Vm::new(&[3, 0, 99]);
+        let timer = vm.map_device(600..601, Timer::default()).unwrap();
+
+        assert_eq!(vm.step().unwrap(), Status::AwaitingInput);
+        assert_eq!(timer.borrow().ticks, 0);
+        vm.insert_input(5);
+        vm.step().unwrap();
+        assert_eq!(timer.borrow().ticks, 1);
+    }
+
+    #[test]
+    fn overlapping_devices() {
+        let mut vm = Vm::new(&[99]);
+        vm.map_device(10..20, Timer::default()).unwrap();
+
+        assert!(vm.map_device(19..21, Timer::default()).is_err());
+        assert!(vm.map_device(20..21, Timer::default()).is_ok());
+    }
+}
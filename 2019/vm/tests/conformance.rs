@@ -0,0 +1,335 @@
+// Runs the example programs from the 2019 puzzle descriptions, and every
+// Intcode puzzle input in the repo, against known results.
+
+use std::collections::HashMap;
+use vm::{Int, Status, Vm};
+
+fn parse(s: &str) -> Vec<Int> {
+    s.trim().split(',').map(|n| n.parse().unwrap()).collect()
+}
+
+// Runs `vm` to completion, returning every output.
+fn outputs(vm: &mut Vm, inputs: &[Int]) -> Vec<Int> {
+    let mut inputs = inputs.iter();
+    let mut out = vec![];
+
+    loop {
+        match vm.step().unwrap() {
+            Status::AwaitingInput => vm.insert_input(*inputs.next().expect("out of input")),
+            Status::Output(o) => out.push(o),
+            Status::Halted => return out,
+            Status::Running => (),
+        }
+    }
+}
+
+fn run(code: &[Int], inputs: &[Int]) -> Vec<Int> {
+    outputs(&mut Vm::new(code), inputs)
+}
+
+#[test]
+fn day02_examples() {
+    let cases: &[(&[Int], &[Int])] = &[
+        (
+            &[1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50],
+            &[3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50],
+        ),
+        (&[1, 0, 0, 0, 99], &[2, 0, 0, 0, 99]),
+        (&[2, 3, 0, 3, 99], &[2, 3, 0, 6, 99]),
+        (&[2, 4, 4, 5, 99, 0], &[2, 4, 4, 5, 99, 9801]),
+        (
+            &[1, 1, 1, 4, 99, 5, 6, 0, 99],
+            &[30, 1, 1, 4, 2, 5, 6, 0, 99],
+        ),
+    ];
+
+    for (code, memory) in cases {
+        let mut vm = Vm::new(code);
+        assert_eq!(outputs(&mut vm, &[]), vec![]);
+
+        for (i, v) in memory.iter().enumerate() {
+            assert_eq!(vm.get(i), *v, "program {:?}, address {}", code, i);
+        }
+    }
+}
+
+#[test]
+fn day05_examples() {
+    const LARGER: &[Int] = &[
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+
+    let cases: &[(&[Int], Int, Int)] = &[
+        (&[3, 0, 4, 0, 99], 42, 42),
+        (&[3, 0, 4, 0, 99], -7, -7),
+        (&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], 8, 1),
+        (&[3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8], 7, 0),
+        (&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], 7, 1),
+        (&[3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8], 8, 0),
+        (&[3, 3, 1108, -1, 8, 3, 4, 3, 99], 8, 1),
+        (&[3, 3, 1108, -1, 8, 3, 4, 3, 99], 9, 0),
+        (&[3, 3, 1107, -1, 8, 3, 4, 3, 99], 7, 1),
+        (&[3, 3, 1107, -1, 8, 3, 4, 3, 99], 8, 0),
+        (
+            &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+            0,
+            0,
+        ),
+        (
+            &[3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9],
+            5,
+            1,
+        ),
+        (&[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1], 0, 0),
+        (&[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1], 5, 1),
+        (LARGER, 7, 999),
+        (LARGER, 8, 1000),
+        (LARGER, 9, 1001),
+    ];
+
+    for (code, input, output) in cases {
+        assert_eq!(run(code, &[*input]), vec![*output], "program {:?}", code);
+    }
+
+    // parameter modes and negative numbers, checked through memory
+    for code in &[&[1002, 4, 3, 4, 33][..], &[1101, 100, -1, 4, 0][..]] {
+        let mut vm = Vm::new(code);
+        outputs(&mut vm, &[]);
+        assert_eq!(vm.get(4), 99);
+    }
+}
+
+#[test]
+fn day09_examples() {
+    let quine = [
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    assert_eq!(run(&quine, &[]), quine.to_vec());
+
+    let out = run(&[1102, 34915192, 34915192, 7, 4, 7, 99, 0], &[]);
+    assert_eq!(out, vec![1219070632396864]);
+    assert_eq!(out[0].to_string().len(), 16);
+
+    assert_eq!(
+        run(&[104, 1125899906842624, 99], &[]),
+        vec![1125899906842624]
+    );
+}
+
+#[test]
+fn day02_input() {
+    let code = parse(include_str!("../../02/input/input.txt"));
+
+    let run = |noun, verb| {
+        let mut vm = Vm::new(&code).with_patches(&[(1, noun), (2, verb)]);
+        outputs(&mut vm, &[]);
+        vm.get(0)
+    };
+
+    assert_eq!(run(12, 2), 3516593);
+    assert_eq!(run(77, 49), 19690720);
+}
+
+#[test]
+fn day05_input() {
+    let code = parse(include_str!("../../05/input/input.txt"));
+
+    let result = Vm::new(&code).run_from_start(&[1]).unwrap();
+    assert_eq!(result.status, Status::Halted);
+    assert_eq!(result.inputs_consumed, 1);
+    assert_eq!(result.diagnostic(), Ok(4601506));
+
+    assert_eq!(run(&code, &[5]), vec![5525561]);
+}
+
+#[test]
+fn day07_input() {
+    let code = parse(include_str!("../../07/input/input.txt"));
+
+    // Runs the amplifiers in a feedback loop until the last one halts.
+    let amplify = |phases: &[Int]| {
+        let mut amps: Vec<Vm> = phases
+            .iter()
+            .map(|p| {
+                let mut vm = Vm::new(&code);
+                vm.insert_input(*p);
+                vm
+            })
+            .collect();
+
+        let mut signal = 0;
+
+        'outer: loop {
+            for (i, vm) in amps.iter_mut().enumerate() {
+                let mut input = Some(signal);
+
+                loop {
+                    match vm.step().unwrap() {
+                        Status::AwaitingInput => vm.insert_input(input.take().unwrap()),
+                        Status::Output(o) => {
+                            signal = o;
+                            break;
+                        }
+                        Status::Halted if i == phases.len() - 1 => break 'outer,
+                        Status::Halted => break,
+                        Status::Running => (),
+                    }
+                }
+            }
+        }
+
+        signal
+    };
+
+    let best = |phases: [Int; 5]| {
+        let mut best = Int::MIN;
+
+        for a in 0..5 {
+            for b in 0..5 {
+                for c in 0..5 {
+                    for d in 0..5 {
+                        for e in 0..5 {
+                            let idx = [a, b, c, d, e];
+                            let mut sorted = idx;
+                            sorted.sort();
+
+                            if sorted == [0, 1, 2, 3, 4] {
+                                let p: Vec<Int> = idx.iter().map(|i| phases[*i]).collect();
+                                best = best.max(amplify(&p));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        best
+    };
+
+    assert_eq!(best([0, 1, 2, 3, 4]), 79723);
+    assert_eq!(best([5, 6, 7, 8, 9]), 70602018);
+}
+
+#[test]
+fn day09_input() {
+    let code = parse(include_str!("../../09/input/input.txt"));
+
+    assert_eq!(run(&code, &[1]), vec![2453265701]);
+    assert_eq!(run(&code, &[2]), vec![80805]);
+}
+
+#[test]
+fn optimized_inputs() {
+    for (input, run_with) in &[
+        (include_str!("../../05/input/input.txt"), [1, 5]),
+        (include_str!("../../09/input/input.txt"), [1, 2]),
+    ] {
+        let code = parse(input);
+
+        for i in run_with {
+            assert_eq!(
+                outputs(&mut Vm::new(&code).optimized(), &[*i]),
+                run(&code, &[*i])
+            );
+        }
+    }
+}
+
+#[test]
+fn day11_input() {
+    let code = parse(include_str!("../../11/input/input.txt"));
+
+    let paint = |initial: Int| {
+        let mut vm = Vm::new(&code);
+        let mut hull = HashMap::new();
+        let (mut x, mut y, mut dx, mut dy) = (0, 0, 0, 1);
+        let mut out = vec![];
+
+        hull.insert((0, 0), initial);
+
+        loop {
+            match vm.step().unwrap() {
+                Status::AwaitingInput => vm.insert_input(*hull.get(&(x, y)).unwrap_or(&0)),
+                Status::Output(o) => out.push(o),
+                Status::Halted => return hull,
+                Status::Running => (),
+            }
+
+            if out.len() == 2 {
+                hull.insert((x, y), out[0]);
+
+                let (ndx, ndy) = if out[1] == 0 { (-dy, dx) } else { (dy, -dx) };
+                dx = ndx;
+                dy = ndy;
+                x += dx;
+                y += dy;
+
+                out.clear();
+            }
+        }
+    };
+
+    assert_eq!(paint(0).len(), 1863);
+
+    let hull = paint(1);
+    let rows: Vec<String> = (-5..=0)
+        .rev()
+        .map(|y| {
+            (1..=39)
+                .map(|x| match hull.get(&(x, y)) {
+                    Some(1) => '#',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect();
+
+    assert_eq!(
+        rows,
+        vec![
+            "###..#....#..#.#....####...##.#....####",
+            "#..#.#....#..#.#.......#....#.#.......#",
+            "###..#....#..#.#......#.....#.#......#.",
+            "#..#.#....#..#.#.....#......#.#.....#..",
+            "#..#.#....#..#.#....#....#..#.#....#...",
+            "###..####..##..####.####..##..####.####",
+        ]
+    );
+}
+
+#[test]
+fn day13_input() {
+    let code = parse(include_str!("../../13/input/input.txt"));
+
+    let blocks = run(&code, &[]).chunks(3).filter(|t| t[2] == 2).count();
+    assert_eq!(blocks, 247);
+
+    let mut vm = Vm::new(&code).with_patches(&[(0, 2)]);
+
+    let (mut ball, mut paddle, mut score): (Int, Int, Int) = (0, 0, 0);
+    let mut out = vec![];
+
+    loop {
+        match vm.step().unwrap() {
+            Status::AwaitingInput => vm.insert_input((ball - paddle).signum()),
+            Status::Output(o) => out.push(o),
+            Status::Halted => break,
+            Status::Running => (),
+        }
+
+        if let [x, y, t] = out[..] {
+            match (x, y, t) {
+                (-1, 0, s) => score = s,
+                (x, _, 3) => paddle = x,
+                (x, _, 4) => ball = x,
+                _ => (),
+            }
+
+            out.clear();
+        }
+    }
+
+    assert_eq!(score, 12954);
+}
@@ -0,0 +1,227 @@
+// Property tests over randomly generated programs. The number of programs per
+// property can be raised with FUZZ_ITERATIONS, and a failing run reproduced
+// with FUZZ_SEED.
+
+use vm::{Error, Int, Status, Vm};
+
+const STEPS: usize = 500;
+
+struct Rng(u64);
+
+impl Rng {
+    fn new() -> Self {
+        let seed = std::env::var("FUZZ_SEED")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0x2019_0c0d);
+
+        Rng(seed | 1)
+    }
+
+    // xorshift64*
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn int(&mut self, lo: Int, hi: Int) -> Int {
+        lo + self.below((hi - lo) as u64) as Int
+    }
+
+    fn opcode(&mut self) -> Int {
+        const OPS: [Int; 10] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 99];
+
+        let op = OPS[self.below(OPS.len() as u64) as usize];
+        let modes = self.int(0, 3) * 100 + self.int(0, 3) * 1000 + self.int(0, 3) * 10000;
+
+        op + modes
+    }
+
+    fn cell(&mut self, len: usize) -> Int {
+        match self.below(20) {
+            0..=8 => self.opcode(),
+            9..=15 => self.int(0, len as Int + 8),
+            16..=17 => self.int(-20, 20),
+            18 => self.next() as Int,
+            _ => [Int::MAX, Int::MIN, 1 << 20, -(1 << 20)][self.below(4) as usize],
+        }
+    }
+
+    fn program(&mut self) -> Vec<Int> {
+        let len = self.int(1, 64) as usize;
+        (0..len).map(|_| self.cell(len)).collect()
+    }
+}
+
+fn iterations() -> usize {
+    std::env::var("FUZZ_ITERATIONS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(2000)
+}
+
+// Steps `vm` until it halts, errors or runs out of steps, feeding inputs from
+// `rng`. Returns every step result along with the state hash after it.
+fn drive(vm: &mut Vm, rng: &mut Rng, steps: usize) -> Vec<(Result<Status, Error>, u64)> {
+    let mut trace = vec![];
+
+    for _ in 0..steps {
+        let result = vm.step();
+
+        if let Ok(Status::AwaitingInput) = result {
+            vm.insert_input(rng.int(-50, 50));
+        }
+
+        let done = !matches!(
+            result,
+            Ok(Status::Running) | Ok(Status::Output(_)) | Ok(Status::AwaitingInput)
+        );
+
+        trace.push((result, vm.state_hash()));
+
+        if done {
+            break;
+        }
+    }
+
+    trace
+}
+
+#[test]
+fn random_programs_do_not_panic() {
+    let mut rng = Rng::new();
+
+    for _ in 0..iterations() {
+        let code = rng.program();
+        let mut vm = Vm::new(&code);
+
+        drive(&mut vm, &mut rng, STEPS);
+    }
+}
+
+#[test]
+fn failed_step_leaves_state_untouched() {
+    let mut rng = Rng::new();
+
+    for _ in 0..iterations() {
+        let code = rng.program();
+        let mut vm = Vm::new(&code);
+
+        if let Some((Err(e), hash)) = drive(&mut vm, &mut rng, STEPS).pop() {
+            if e != Error::AlreadyHalted {
+                assert_eq!(vm.step(), Err(e), "program: {:?}", code);
+                assert_eq!(vm.state_hash(), hash, "program: {:?}", code);
+            }
+        }
+    }
+}
+
+#[test]
+fn reset_restores_initial_state() {
+    let mut rng = Rng::new();
+
+    for _ in 0..iterations() {
+        let code = rng.program();
+        let fresh = Vm::new(&code);
+
+        let mut vm = fresh.clone();
+        drive(&mut vm, &mut rng, STEPS);
+        vm.reset();
+
+        assert_eq!(vm.state_hash(), fresh.state_hash(), "program: {:?}", code);
+
+        let mut fresh = fresh;
+        let seed = rng.next();
+
+        assert_eq!(
+            drive(&mut vm, &mut Rng(seed | 1), STEPS),
+            drive(&mut fresh, &mut Rng(seed | 1), STEPS),
+            "program: {:?}",
+            code
+        );
+    }
+}
+
+#[test]
+fn clones_evolve_identically() {
+    let mut rng = Rng::new();
+
+    for _ in 0..iterations() {
+        let code = rng.program();
+        let mut vm = Vm::new(&code);
+
+        let prefix = rng.below(50) as usize;
+        drive(&mut vm, &mut rng, prefix);
+
+        let mut clone = vm.clone();
+        assert_eq!(clone.state_hash(), vm.state_hash());
+
+        let seed = rng.next();
+
+        assert_eq!(
+            drive(&mut vm, &mut Rng(seed | 1), STEPS),
+            drive(&mut clone, &mut Rng(seed | 1), STEPS),
+            "program: {:?}",
+            code
+        );
+    }
+}
+
+#[test]
+fn optimized_matches_plain() {
+    let mut rng = Rng::new();
+
+    let events = |trace: Vec<(Result<Status, Error>, u64)>| -> Vec<_> {
+        trace
+            .into_iter()
+            .filter(|(r, _)| *r != Ok(Status::Running))
+            .collect()
+    };
+
+    for _ in 0..iterations() {
+        let code = rng.program();
+        let seed = rng.next();
+
+        let plain = events(drive(&mut Vm::new(&code), &mut Rng(seed | 1), STEPS));
+        let optimized = events(drive(
+            &mut Vm::new(&code).optimized(),
+            &mut Rng(seed | 1),
+            STEPS,
+        ));
+
+        // the optimized vm retires fused instructions in one step, so it may
+        // get further within the step budget
+        assert!(plain.len() <= optimized.len(), "program: {:?}", code);
+        assert_eq!(plain[..], optimized[..plain.len()], "program: {:?}", code);
+    }
+}
+
+#[test]
+fn step_after_halt() {
+    let mut rng = Rng::new();
+    let mut halted = 0;
+
+    for _ in 0..iterations() {
+        let code: Vec<Int> = rng.program().into_iter().chain(Some(99)).collect();
+
+        let mut vm = Vm::new(&code);
+        drive(&mut vm, &mut rng, STEPS);
+
+        if vm.halted() {
+            halted += 1;
+
+            for _ in 0..3 {
+                assert_eq!(vm.step(), Err(Error::AlreadyHalted));
+                assert!(vm.halted());
+            }
+        }
+    }
+
+    assert!(halted > 0);
+}
@@ -0,0 +1,104 @@
+use aoc::days;
+use common::bench::{self, Baseline, Change, Row, SortBy};
+use std::path::PathBuf;
+
+const USAGE: &str = "usage: bench [--runs <n>] [--sort day|min|median|max] [--baseline <file>]
+             [--threshold <percent>] [--save <file>] (<year> (<day>... | --all) | --all)
+
+  --runs       times to solve each day (default 5)
+  --sort       order of the table; timings sort slowest first (default day)
+  --baseline   compare the medians with a saved baseline, failing on regressions
+  --threshold  how much slower than the baseline counts as a regression (default 10)
+  --save       write the timings as a new baseline";
+
+struct Options {
+    runs: usize,
+    sort: SortBy,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+    save: Option<PathBuf>,
+    days: Vec<String>,
+}
+
+fn main() {
+    let opts = parse_args().unwrap_or_else(|| exit(USAGE));
+    let args: Vec<&str> = opts.days.iter().map(|a| a.as_ref()).collect();
+    let days = days::select(&args).unwrap_or_else(|e| exit(&format!("{}\n\n{}", e, USAGE)));
+    days::warn_ignored_input(&days);
+
+    let baseline = match &opts.baseline {
+        Some(path) => Baseline::load(path).unwrap_or_else(|e| exit(&e.to_string())),
+        None => Baseline::default(),
+    };
+
+    let mut rows = vec![];
+    let mut current = Baseline::default();
+    let alone = days.len() == 1;
+
+    for day in days {
+        eprintln!("benchmarking {}", day.name());
+
+        let stats = day
+            .input(alone)
+            .and_then(|input| {
+                bench::measure(opts.runs, || (day.solve)(&input)).map_err(|e| e.to_string())
+            })
+            .unwrap_or_else(|e| exit(&format!("{}: {}", day.name(), e)));
+
+        rows.push(Row {
+            name: day.name(),
+            stats,
+            change: Change::between(&stats, baseline.0.get(&day.name()), opts.threshold),
+        });
+
+        current.0.insert(day.name(), stats);
+    }
+
+    print!("{}", bench::table(&rows, opts.sort));
+
+    if let Some(path) = &opts.save {
+        current.save(path).unwrap_or_else(|e| exit(&e.to_string()));
+    }
+
+    if rows
+        .iter()
+        .any(|r| matches!(r.change, Change::Regressed(_)))
+    {
+        std::process::exit(1);
+    }
+}
+
+fn parse_args() -> Option<Options> {
+    let mut opts = Options {
+        runs: 5,
+        sort: SortBy::Name,
+        baseline: None,
+        threshold: 0.1,
+        save: None,
+        days: vec![],
+    };
+
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_ref() {
+            "--runs" => opts.runs = args.next()?.parse().ok().filter(|n| *n > 0)?,
+            "--sort" => opts.sort = SortBy::parse(&args.next()?)?,
+            "--baseline" => opts.baseline = Some(args.next()?.into()),
+            "--threshold" => {
+                let percent: f64 = args.next()?.parse().ok().filter(|p| *p >= 0.0)?;
+                opts.threshold = percent / 100.0;
+            }
+            "--save" => opts.save = Some(args.next()?.into()),
+            "--help" => return None,
+            _ => opts.days.push(arg),
+        }
+    }
+
+    Some(opts)
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2)
+}
@@ -0,0 +1,119 @@
+use common::input::{InputError, Source, INPUT_VAR};
+use common::solution::{solve, Report};
+use std::path::Path;
+
+pub struct Day {
+    pub year: u32,
+    pub day: u32,
+    // the day's crate, which holds its input
+    pub dir: &'static str,
+    pub solve: fn(&str) -> Result<Report, InputError>,
+}
+
+impl Day {
+    // Such as `2019/13`.
+    pub fn name(&self) -> String {
+        format!("{}/{:02}", self.year, self.day)
+    }
+
+    // `AOC_INPUT` names a single day's input, so it's only used when `alone`,
+    // that is when this is the only day selected (see `warn_ignored_input`).
+    pub fn input(&self, alone: bool) -> Result<String, String> {
+        let source = match Source::from_env() {
+            Some(source) if alone => source,
+            // don't wait on stdin for a day that has no input file
+            _ => Source::shipped(Path::new(self.dir)).ok_or("no input file")?,
+        };
+
+        source.read().map_err(|e| e.to_string())
+    }
+
+    pub fn solve(&self, alone: bool) -> Result<Report, String> {
+        (self.solve)(&self.input(alone)?).map_err(|e| e.to_string())
+    }
+}
+
+macro_rules! day {
+    ($year:literal, $day:literal, $solution:ty) => {
+        Day {
+            year: $year,
+            day: $day,
+            dir: concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../",
+                stringify!($year),
+                "/",
+                stringify!($day)
+            ),
+            solve: solve::<$solution>,
+        }
+    };
+}
+
+// Days are zero-padded so they match their directories.
+#[allow(clippy::zero_prefixed_literal)]
+pub const DAYS: &[Day] = &[
+    day!(2015, 01, aoc2015_day01::Day01),
+    day!(2015, 02, aoc2015_day02::Day02),
+    day!(2015, 03, aoc2015_day03::Day03),
+    day!(2015, 04, aoc2015_day04::Day04),
+    day!(2015, 05, aoc2015_day05::Day05),
+    day!(2015, 06, aoc2015_day06::Day06),
+    day!(2015, 07, aoc2015_day07::Day07),
+    day!(2015, 08, aoc2015_day08::Day08),
+    day!(2019, 01, aoc2019_day01::Day01),
+    day!(2019, 02, aoc2019_day02::Day02),
+    day!(2019, 03, aoc2019_day03::Day03),
+    day!(2019, 04, aoc2019_day04::Day04),
+    day!(2019, 05, aoc2019_day05::Day05),
+    day!(2019, 06, aoc2019_day06::Day06),
+    day!(2019, 07, aoc2019_day07::Day07),
+    day!(2019, 08, aoc2019_day08::Day08),
+    day!(2019, 09, aoc2019_day09::Day09),
+    day!(2019, 10, aoc2019_day10::Day10),
+    day!(2019, 11, aoc2019_day11::Day11),
+    day!(2019, 12, aoc2019_day12::Day12),
+    day!(2019, 13, aoc2019_day13::Day13),
+];
+
+// Picks days from the command line: `--all`, `<year> --all` or
+// `<year> <day>...`.
+// Lets the user know their `AOC_INPUT` isn't used for any of `days`.
+pub fn warn_ignored_input(days: &[&Day]) {
+    if days.len() > 1 && Source::from_env().is_some() {
+        eprintln!(
+            "warning: ignoring {}, which only applies when a single day is selected",
+            INPUT_VAR
+        );
+    }
+}
+
+pub fn select(args: &[&str]) -> Result<Vec<&'static Day>, String> {
+    let (year, days) = match args {
+        ["--all"] => return Ok(DAYS.iter().collect()),
+        [year, days @ ..] if !days.is_empty() => (year, days),
+        _ => return Err("expected --all or <year> (<day>... | --all)".to_string()),
+    };
+
+    let year: u32 = year
+        .parse()
+        .map_err(|_| format!("invalid year {:?}", year))?;
+    let in_year = || DAYS.iter().filter(move |d| d.year == year);
+
+    if in_year().next().is_none() {
+        return Err(format!("no days for {}", year));
+    }
+
+    match days {
+        ["--all"] => Ok(in_year().collect()),
+        _ => days
+            .iter()
+            .map(|n| {
+                let n: u32 = n.parse().map_err(|_| format!("invalid day {:?}", n))?;
+                in_year()
+                    .find(|d| d.day == n)
+                    .ok_or_else(|| format!("no solution for {} day {}", year, n))
+            })
+            .collect(),
+    }
+}
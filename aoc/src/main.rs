@@ -0,0 +1,184 @@
+use aoc::days::{self, Day, DAYS};
+use common::answers::{self, Answers, Verdict};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+const USAGE: &str = "usage: aoc (run | verify | record) <year> (<day>... | --all)
+       aoc (run | verify | record) --all
+       aoc list
+
+  run     solve the given days against their input files, timing each part
+  verify  compare the answers with the ones recorded in answers.toml
+  record  solve the given days and, once confirmed, record their answers
+
+AOC_INPUT names an input file to use instead, or - for stdin, when a single
+day is selected.";
+
+enum Command {
+    Run,
+    Verify,
+    Record,
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(|a| a.as_ref()).collect();
+
+    let command = match args.first() {
+        Some(&"list") if args.len() == 1 => {
+            for d in DAYS {
+                println!("{} day {:02}", d.year, d.day);
+            }
+
+            return;
+        }
+        Some(&"run") => Command::Run,
+        Some(&"verify") => Command::Verify,
+        Some(&"record") => Command::Record,
+        _ => exit(USAGE),
+    };
+
+    let days = days::select(&args[1..]).unwrap_or_else(|e| exit(&format!("{}\n\n{}", e, USAGE)));
+    days::warn_ignored_input(&days);
+
+    let alone = days.len() == 1;
+    let ok = match command {
+        Command::Run => days.iter().filter(|d| !run(d, alone)).count() == 0,
+        Command::Verify => verify(&days),
+        Command::Record => days.iter().filter(|d| !record(d, alone)).count() == 0,
+    };
+
+    if !ok {
+        std::process::exit(1);
+    }
+}
+
+fn indented<T: ToString>(s: T) {
+    for line in s.to_string().lines() {
+        println!("  {}", line);
+    }
+}
+
+// Prints both answers for `day`, returning false if its input is missing or
+// invalid. `alone` is whether it's the only day being run.
+fn run(day: &Day, alone: bool) -> bool {
+    println!("{} day {:02}", day.year, day.day);
+
+    match day.solve(alone) {
+        Ok(report) => {
+            indented(report);
+            true
+        }
+        Err(e) => {
+            indented(e);
+            false
+        }
+    }
+}
+
+// Checks every day against its recorded answers, returning false if any of
+// them failed to run or got a different answer.
+fn verify(days: &[&Day]) -> bool {
+    let (mut pass, mut fail, mut unknown) = (0, 0, 0);
+    let alone = days.len() == 1;
+
+    for day in days {
+        println!("{} day {:02}", day.year, day.day);
+
+        let verdicts = Answers::load(Path::new(day.dir))
+            .map_err(|e| e.to_string())
+            .and_then(|answers| Ok(answers.verify(&day.solve(alone)?)));
+
+        let verdicts = match verdicts {
+            Ok(verdicts) => verdicts,
+            Err(e) => {
+                indented(e);
+                fail += 1;
+                continue;
+            }
+        };
+
+        for (i, v) in verdicts.iter().enumerate() {
+            indented(format!("part {}: {}", i + 1, v));
+        }
+
+        if verdicts.iter().any(|v| matches!(v, Verdict::Fail { .. })) {
+            fail += 1;
+        } else if verdicts.contains(&Verdict::Unknown) {
+            unknown += 1;
+        } else {
+            pass += 1;
+        }
+    }
+
+    println!("{} passed, {} failed, {} unknown", pass, fail, unknown);
+    fail == 0
+}
+
+// Solves `day` and asks before recording its answers, returning false if it
+// couldn't be solved or saved.
+fn record(day: &Day, alone: bool) -> bool {
+    let dir = Path::new(day.dir);
+    println!("{} day {:02}", day.year, day.day);
+
+    let solved = Answers::load(dir)
+        .map_err(|e| e.to_string())
+        .and_then(|answers| Ok((answers, day.solve(alone)?)));
+
+    let (recorded, report) = match solved {
+        Ok(solved) => solved,
+        Err(e) => {
+            indented(e);
+            return false;
+        }
+    };
+
+    indented(&report);
+    let verdicts = recorded.verify(&report);
+
+    if verdicts.iter().all(|v| *v == Verdict::Pass) {
+        indented("answers already recorded");
+        return true;
+    }
+
+    for (i, v) in verdicts.iter().enumerate() {
+        if let Verdict::Fail { expected, .. } = v {
+            indented(format!("part {} was recorded as {}", i + 1, expected));
+        }
+    }
+
+    let file = format!("{}/{}", day.name(), answers::FILE);
+    if !confirm(&format!("record to {}?", file)) {
+        return true;
+    }
+
+    let answers = Answers {
+        parts: [
+            Some(report.parts[0].value.clone()),
+            Some(report.parts[1].value.clone()),
+        ],
+    };
+
+    match answers.save(dir) {
+        Ok(()) => true,
+        Err(e) => {
+            indented(e);
+            false
+        }
+    }
+}
+
+fn confirm(question: &str) -> bool {
+    print!("  {} [y/N] ", question);
+    io::stdout().flush().ok();
+
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line).ok();
+
+    matches!(line.trim(), "y" | "Y" | "yes")
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(2)
+}
@@ -0,0 +1,303 @@
+use crate::input::InputError;
+use crate::solution::Report;
+use std::fmt::{self, Write};
+use std::path::Path;
+
+// Where a day's answers are recorded, next to its input.
+pub const FILE: &str = "answers.toml";
+
+// The known answers to a day. The file is a small subset of TOML: `part1` and
+// `part2` keys holding strings (multi-line ones for rendered images) or
+// integers, plus comments and blank lines.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Answers {
+    pub parts: [Option<String>; 2],
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Unknown => write!(f, "unknown"),
+            Verdict::Fail { expected, actual } => {
+                match expected.contains('\n') || actual.contains('\n') {
+                    true => write!(f, "fail, expected\n{}\ngot\n{}", expected, actual),
+                    false => write!(f, "fail, expected {} got {}", expected, actual),
+                }
+            }
+        }
+    }
+}
+
+impl Answers {
+    pub fn parse(s: &str) -> Result<Self, InputError> {
+        let mut answers = Answers::default();
+        let mut lines = s.lines().enumerate();
+
+        while let Some((index, line)) = lines.next() {
+            let error = |reason: &str| InputError::Parse {
+                index,
+                record: line.to_string(),
+                reason: reason.to_string(),
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error("expected key = value"))?;
+            let part = match key.trim() {
+                "part1" => 0,
+                "part2" => 1,
+                _ => return Err(error("expected part1 or part2")),
+            };
+
+            let value = value.trim();
+            let value = if let Some(rest) = value.strip_prefix("\"\"\"") {
+                // a newline straight after the opening quotes isn't part of it
+                let mut raw = rest.to_string();
+                while !raw.contains("\"\"\"") {
+                    let (_, next) = lines.next().ok_or_else(|| error("unterminated string"))?;
+                    raw.push('\n');
+                    raw.push_str(next);
+                }
+
+                let (body, tail) = raw.split_once("\"\"\"").unwrap();
+                if !is_comment(tail) {
+                    return Err(error("unexpected text after string"));
+                }
+
+                unescape(body.strip_prefix('\n').unwrap_or(body)).map_err(error)?
+            } else if let Some(rest) = value.strip_prefix('"') {
+                let end = closing_quote(rest).ok_or_else(|| error("unterminated string"))?;
+                if !is_comment(&rest[end + 1..]) {
+                    return Err(error("unexpected text after string"));
+                }
+
+                unescape(&rest[..end]).map_err(error)?
+            } else {
+                let n = value.split('#').next().unwrap().trim();
+                n.parse::<i64>()
+                    .map_err(|_| error("expected a string or integer"))?;
+                n.to_string()
+            };
+
+            answers.parts[part] = Some(value);
+        }
+
+        Ok(answers)
+    }
+
+    // Reads the answers recorded in `dir`, which are all unknown if there's
+    // no answers file yet.
+    pub fn load(dir: &Path) -> Result<Self, InputError> {
+        let path = dir.join(FILE);
+
+        match std::fs::read_to_string(&path) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(InputError::File(path, e)),
+        }
+    }
+
+    pub fn save(&self, dir: &Path) -> Result<(), InputError> {
+        let path = dir.join(FILE);
+        std::fs::write(&path, self.to_toml()).map_err(|e| InputError::File(path, e))
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut s = String::new();
+
+        for (i, part) in self.parts.iter().enumerate() {
+            let value = match part {
+                Some(v) if v.contains('\n') => format!("\"\"\"\n{}\"\"\"", escape(v, false)),
+                Some(v) => format!("\"{}\"", escape(v, true)),
+                None => continue,
+            };
+
+            writeln!(s, "part{} = {}", i + 1, value).unwrap();
+        }
+
+        s
+    }
+
+    // Compares each part of `report` with the recorded answer. Trailing
+    // whitespace is ignored, since rendered images tend to pick some up.
+    pub fn verify(&self, report: &Report) -> [Verdict; 2] {
+        let verdict = |expected: &Option<String>, actual: &str| match expected {
+            Some(e) if e.trim_end() == actual.trim_end() => Verdict::Pass,
+            Some(e) => Verdict::Fail {
+                expected: e.trim_end().to_string(),
+                actual: actual.trim_end().to_string(),
+            },
+            None => Verdict::Unknown,
+        };
+
+        [
+            verdict(&self.parts[0], &report.parts[0].value),
+            verdict(&self.parts[1], &report.parts[1].value),
+        ]
+    }
+}
+
+fn is_comment(s: &str) -> bool {
+    let s = s.trim();
+    s.is_empty() || s.starts_with('#')
+}
+
+// The index of the first unescaped quote in `s`.
+fn closing_quote(s: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (i, c) in s.char_indices() {
+        match (c, escaped) {
+            ('"', false) => return Some(i),
+            ('\\', false) => escaped = true,
+            _ => escaped = false,
+        }
+    }
+
+    None
+}
+
+fn escape(s: &str, newlines: bool) -> String {
+    let mut ret = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '\\' => ret.push_str("\\\\"),
+            '"' => ret.push_str("\\\""),
+            '\n' if newlines => ret.push_str("\\n"),
+            '\t' => ret.push_str("\\t"),
+            '\r' => ret.push_str("\\r"),
+            c => ret.push(c),
+        }
+    }
+
+    ret
+}
+
+fn unescape(s: &str) -> Result<String, &'static str> {
+    let mut ret = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            ret.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('\\') => ret.push('\\'),
+            Some('"') => ret.push('"'),
+            Some('n') => ret.push('\n'),
+            Some('t') => ret.push('\t'),
+            Some('r') => ret.push('\r'),
+            Some('u') => ret.push(unicode(&mut chars, 4)?),
+            Some('U') => ret.push(unicode(&mut chars, 8)?),
+            _ => return Err("invalid escape"),
+        }
+    }
+
+    Ok(ret)
+}
+
+// The character named by the `digits` hex digits after a `\u` or `\U`.
+fn unicode(chars: &mut std::str::Chars, digits: usize) -> Result<char, &'static str> {
+    let hex: String = chars.by_ref().take(digits).collect();
+    if hex.len() != digits || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("invalid unicode escape");
+    }
+
+    char::from_u32(u32::from_str_radix(&hex, 16).unwrap()).ok_or("invalid unicode escape")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solution::Answer;
+    use std::time::Duration;
+
+    fn report(part1: &str, part2: &str) -> Report {
+        let answer = |value: &str| Answer {
+            value: value.to_string(),
+            elapsed: Duration::default(),
+        };
+
+        Report {
+            parse: Duration::default(),
+            parts: [answer(part1), answer(part2)],
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse(
+            "# day 8\npart1 = 1560 # checksum\npart2 = \"\"\"\n# #\n \\\"\n\"\"\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.parts[0].as_deref(), Some("1560"));
+        assert_eq!(answers.parts[1].as_deref(), Some("# #\n \"\n"));
+
+        let escaped = Answers::parse(r#"part1 = "\u0041\U0001F600""#).unwrap();
+        assert_eq!(escaped.parts[0].as_deref(), Some("A\u{1F600}"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let index = |s| match Answers::parse(s) {
+            Err(InputError::Parse { index, .. }) => Some(index),
+            _ => None,
+        };
+
+        assert_eq!(index("\npart3 = 1"), Some(1));
+        assert_eq!(index("part1 = \"abc"), Some(0));
+        assert_eq!(index("part1 = \"\"\"\nabc"), Some(0));
+        assert_eq!(index("part1 = abc"), Some(0));
+        assert_eq!(index("part1 = \"\\u41\""), Some(0));
+        assert_eq!(index("part1 = \"\\u+041\""), Some(0));
+        assert_eq!(index("part1 = \"\\U0000d800\""), Some(0));
+    }
+
+    #[test]
+    fn round_trip() {
+        let answers = Answers {
+            parts: [
+                Some("a \"b\" \\ c".to_string()),
+                Some(" ## \n#  #\n".to_string()),
+            ],
+        };
+
+        assert_eq!(Answers::parse(&answers.to_toml()).unwrap(), answers);
+    }
+
+    #[test]
+    fn verify() {
+        let answers = Answers {
+            parts: [Some("42".to_string()), None],
+        };
+
+        assert_eq!(
+            answers.verify(&report("42", "7")),
+            [Verdict::Pass, Verdict::Unknown]
+        );
+        assert_eq!(
+            answers.verify(&report("41", "7"))[0],
+            Verdict::Fail {
+                expected: "42".to_string(),
+                actual: "41".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,353 @@
+use crate::input::InputError;
+use crate::solution::Report;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+use std::time::Duration;
+
+// How long a day takes to parse its input and solve both parts, over a number
+// of runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+
+        Some(Stats {
+            runs: samples.len(),
+            min: *samples.first()?,
+            median: samples[samples.len() / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+// Solves a day `runs` times, at least once, timing each run.
+pub fn measure<F>(runs: usize, mut solve: F) -> Result<Stats, InputError>
+where
+    F: FnMut() -> Result<Report, InputError>,
+{
+    let samples = (0..runs.max(1))
+        .map(|_| {
+            let r = solve()?;
+            Ok(r.parse + r.parts[0].elapsed + r.parts[1].elapsed)
+        })
+        .collect::<Result<_, InputError>>()?;
+
+    Ok(Stats::from_samples(samples).unwrap())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    // the median moved by less than the threshold
+    Unchanged(f64),
+    Regressed(f64),
+    Improved(f64),
+    // there's nothing to compare with
+    New,
+}
+
+// Changes smaller than this are timer noise, however large they are relative
+// to a day that only takes microseconds.
+pub const NOISE: Duration = Duration::from_micros(100);
+
+impl Change {
+    // Compares medians. `threshold` is the relative change that counts, so
+    // 0.1 flags anything more than 10% slower or faster.
+    pub fn between(current: &Stats, baseline: Option<&Stats>, threshold: f64) -> Self {
+        let baseline = match baseline {
+            Some(b) if b.median > Duration::ZERO => b,
+            _ => return Change::New,
+        };
+
+        let ratio = current.median.as_secs_f64() / baseline.median.as_secs_f64() - 1.0;
+        let noise = match current.median > baseline.median {
+            true => current.median - baseline.median < NOISE,
+            false => baseline.median - current.median < NOISE,
+        };
+
+        match ratio {
+            r if r > threshold && !noise => Change::Regressed(r),
+            r if r < -threshold && !noise => Change::Improved(r),
+            r => Change::Unchanged(r),
+        }
+    }
+}
+
+// Saved timings, keyed by day (e.g. `2019/12`). Stored as JSON, one day per
+// line so baselines diff nicely, with every duration in nanoseconds:
+//
+//     {
+//       "2019/12": {"runs": 10, "min": 81000000, "median": 83000000, "max": 90000000}
+//     }
+//
+// Only that shape is read back, not JSON in general.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline(pub BTreeMap<String, Stats>);
+
+impl Baseline {
+    pub fn parse(s: &str) -> Result<Self, InputError> {
+        let mut days = BTreeMap::new();
+        let lines: Vec<&str> = s.lines().collect();
+
+        if lines.first().map(|l| l.trim()) != Some("{") {
+            return Err(InputError::Invalid(
+                "baseline: expected an object".to_string(),
+            ));
+        }
+
+        for (index, line) in lines.iter().enumerate() {
+            if matches!(line.trim(), "{" | "}" | "") {
+                continue;
+            }
+
+            let (day, stats) = parse_day(line).map_err(|reason| InputError::Parse {
+                index,
+                record: line.to_string(),
+                reason: reason.to_string(),
+            })?;
+
+            days.insert(day, stats);
+        }
+
+        Ok(Baseline(days))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, InputError> {
+        let s =
+            std::fs::read_to_string(path).map_err(|e| InputError::File(path.to_path_buf(), e))?;
+
+        Self::parse(&s)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), InputError> {
+        std::fs::write(path, self.to_json()).map_err(|e| InputError::File(path.to_path_buf(), e))
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut s = String::from("{\n");
+
+        for (i, (day, stats)) in self.0.iter().enumerate() {
+            let comma = if i + 1 < self.0.len() { "," } else { "" };
+
+            writeln!(
+                s,
+                "  \"{}\": {{\"runs\": {}, \"min\": {}, \"median\": {}, \"max\": {}}}{}",
+                day,
+                stats.runs,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos(),
+                comma
+            )
+            .unwrap();
+        }
+
+        s.push_str("}\n");
+        s
+    }
+}
+
+// Reads one `"day": {"runs": .., "min": .., "median": .., "max": ..}` line.
+fn parse_day(line: &str) -> Result<(String, Stats), &'static str> {
+    let line = line.trim().trim_end_matches(',');
+    let (day, fields) = line
+        .strip_prefix('"')
+        .and_then(|l| l.split_once("\":"))
+        .ok_or("expected \"day\": {...}")?;
+
+    let fields = fields
+        .trim()
+        .strip_prefix('{')
+        .and_then(|f| f.strip_suffix('}'))
+        .ok_or("expected {...}")?;
+
+    let mut values = BTreeMap::new();
+    for field in fields.split(',') {
+        let (name, value) = field.split_once(':').ok_or("expected \"name\": value")?;
+        let value: u64 = value.trim().parse().map_err(|_| "expected an integer")?;
+        values.insert(name.trim().trim_matches('"'), value);
+    }
+
+    let field = |name| values.get(name).copied().ok_or("missing a field");
+
+    Ok((
+        day.to_string(),
+        Stats {
+            runs: field("runs")? as usize,
+            min: Duration::from_nanos(field("min")?),
+            median: Duration::from_nanos(field("median")?),
+            max: Duration::from_nanos(field("max")?),
+        },
+    ))
+}
+
+#[derive(Clone, Debug)]
+pub struct Row {
+    pub name: String,
+    pub stats: Stats,
+    pub change: Change,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortBy {
+    Name,
+    Min,
+    Median,
+    Max,
+}
+
+impl SortBy {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "name" | "day" => Some(SortBy::Name),
+            "min" => Some(SortBy::Min),
+            "median" => Some(SortBy::Median),
+            "max" => Some(SortBy::Max),
+            _ => None,
+        }
+    }
+}
+
+// Lays the rows out in a table. Timings sort slowest first.
+pub fn table(rows: &[Row], sort: SortBy) -> String {
+    let mut rows: Vec<&Row> = rows.iter().collect();
+
+    match sort {
+        SortBy::Name => rows.sort_by(|a, b| a.name.cmp(&b.name)),
+        SortBy::Min => rows.sort_by_key(|r| std::cmp::Reverse(r.stats.min)),
+        SortBy::Median => rows.sort_by_key(|r| std::cmp::Reverse(r.stats.median)),
+        SortBy::Max => rows.sort_by_key(|r| std::cmp::Reverse(r.stats.max)),
+    }
+
+    let mut ret = format!(
+        "{:<10} {:>5} {:>12} {:>12} {:>12}  {}\n",
+        "day", "runs", "min", "median", "max", "vs baseline"
+    );
+
+    for r in rows {
+        let change = match r.change {
+            Change::New => "-".to_string(),
+            Change::Unchanged(c) => format!("{:+.1}%", c * 100.0),
+            Change::Improved(c) => format!("{:+.1}% improved", c * 100.0),
+            Change::Regressed(c) => format!("{:+.1}% REGRESSED", c * 100.0),
+        };
+
+        writeln!(
+            ret,
+            "{:<10} {:>5} {:>12} {:>12} {:>12}  {}",
+            r.name,
+            r.stats.runs,
+            format!("{:.2?}", r.stats.min),
+            format!("{:.2?}", r.stats.median),
+            format!("{:.2?}", r.stats.max),
+            change
+        )
+        .unwrap();
+    }
+
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stats(ms: u64) -> Stats {
+        let d = Duration::from_millis(ms);
+        Stats {
+            runs: 3,
+            min: d,
+            median: d,
+            max: d,
+        }
+    }
+
+    #[test]
+    fn from_samples() {
+        let ms = |v: &[u64]| v.iter().map(|n| Duration::from_millis(*n)).collect();
+        let s = Stats::from_samples(ms(&[5, 1, 9, 3])).unwrap();
+
+        assert_eq!(
+            (s.runs, s.min, s.median, s.max),
+            (4, ms(&[1])[0], ms(&[5])[0], ms(&[9])[0])
+        );
+        assert_eq!(Stats::from_samples(vec![]), None);
+    }
+
+    #[test]
+    fn change() {
+        assert_eq!(Change::between(&stats(10), None, 0.1), Change::New);
+        assert!(matches!(
+            Change::between(&stats(12), Some(&stats(10)), 0.1),
+            Change::Regressed(r) if (r - 0.2).abs() < 1e-9
+        ));
+        assert!(matches!(
+            Change::between(&stats(8), Some(&stats(10)), 0.1),
+            Change::Improved(_)
+        ));
+        assert!(matches!(
+            Change::between(&stats(10), Some(&stats(10)), 0.1),
+            Change::Unchanged(_)
+        ));
+
+        let fast = |us| Stats {
+            median: Duration::from_micros(us),
+            ..stats(0)
+        };
+        assert!(matches!(
+            Change::between(&fast(20), Some(&fast(10)), 0.1),
+            Change::Unchanged(_)
+        ));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut b = Baseline::default();
+        b.0.insert("2015/04".to_string(), stats(2600));
+        b.0.insert("2019/12".to_string(), stats(90));
+
+        assert_eq!(Baseline::parse(&b.to_json()).unwrap(), b);
+    }
+
+    #[test]
+    fn baseline_errors() {
+        let index = |s| match Baseline::parse(s) {
+            Err(InputError::Parse { index, .. }) => Some(index),
+            _ => None,
+        };
+
+        assert!(Baseline::parse("[]").is_err());
+        assert_eq!(index("{\n  \"2019/01\": {\"runs\": 1}\n}"), Some(1));
+        assert_eq!(index("{\n\n  \"2019/01\": {\"runs\": x}\n}"), Some(2));
+        assert_eq!(index("{\n  2019/01: {}\n}"), Some(1));
+    }
+
+    #[test]
+    fn sorted_table() {
+        let rows = [
+            Row {
+                name: "2019/01".to_string(),
+                stats: stats(1),
+                change: Change::New,
+            },
+            Row {
+                name: "2019/02".to_string(),
+                stats: stats(5),
+                change: Change::Regressed(0.5),
+            },
+        ];
+
+        let t = table(&rows, SortBy::Median);
+        let lines: Vec<&str> = t.lines().collect();
+
+        assert!(lines[1].starts_with("2019/02") && lines[1].ends_with("+50.0% REGRESSED"));
+        assert!(lines[2].starts_with("2019/01") && lines[2].ends_with(" -"));
+    }
+}
@@ -0,0 +1,519 @@
+use crate::input::InputError;
+use crate::point::Point2;
+use std::collections::hash_map::{Entry, HashMap};
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut, RangeInclusive};
+
+// Offsets to the cells sharing an edge, clockwise from up.
+pub const ORTHOGONAL: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+// Offsets to every surrounding cell, clockwise from up.
+pub const SURROUNDING: [(i64, i64); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+// A fixed-size grid stored row by row. Cells are addressed as (x, y) with
+// (0, 0) in the top left, so y grows downwards like the lines of a map.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    // Returns `None` unless `cells` fills whole rows of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        match width {
+            0 if cells.is_empty() => Some(Self {
+                width,
+                height: 0,
+                cells,
+            }),
+            0 => None,
+            w if cells.len().is_multiple_of(w) => Some(Self {
+                width,
+                height: cells.len() / w,
+                cells,
+            }),
+            _ => None,
+        }
+    }
+
+    // Parses a character map, one row per line, with `f` turning each
+    // character into a cell. Every row must be the same width.
+    pub fn parse<F, E>(s: &str, mut f: F) -> Result<Self, InputError>
+    where
+        F: FnMut(char) -> Result<T, E>,
+        E: fmt::Display,
+    {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for (index, line) in s.lines().map(|l| l.trim_end_matches('\r')).enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let error = |reason: String| InputError::Parse {
+                index,
+                record: line.to_string(),
+                reason,
+            };
+
+            let w = line.chars().count();
+            if *width.get_or_insert(w) != w {
+                return Err(error(format!(
+                    "expected {} cells, found {}",
+                    width.unwrap(),
+                    w
+                )));
+            }
+
+            for c in line.chars() {
+                cells.push(f(c).map_err(|e| error(format!("{:?}: {}", c, e)))?);
+            }
+        }
+
+        Ok(Self::from_vec(width.unwrap_or(0), cells).unwrap())
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    // Whether (x, y) is on the grid. Takes signed coordinates so positions
+    // stepped off the edge can be checked.
+    pub fn in_bounds(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        match self.in_bounds(x, y) {
+            true => Some(&self.cells[y as usize * self.width + x as usize]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        match self.in_bounds(x, y) {
+            true => Some(&mut self.cells[y as usize * self.width + x as usize]),
+            false => None,
+        }
+    }
+
+    // Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let w = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, c)| ((i % w, i / w), c))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    // The cells sharing an edge with (x, y) that are on the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.around(x, y, &ORTHOGONAL)
+    }
+
+    // The up to eight cells surrounding (x, y), diagonals included.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.around(x, y, &SURROUNDING)
+    }
+
+    fn around<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(i64, i64)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            self.get(nx, ny).map(|c| ((nx as usize, ny as usize), c))
+        })
+    }
+
+    // Draws the grid a row per line, with `f` picking each cell's character.
+    pub fn render<F: Fn(&T) -> char>(&self, f: F) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is out of bounds",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is out of bounds",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+// The smallest box holding every cell of a sparse grid, inclusive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Bounds {
+    pub min_x: i64,
+    pub max_x: i64,
+    pub min_y: i64,
+    pub max_y: i64,
+}
+
+impl Bounds {
+    pub fn width(&self) -> usize {
+        (self.max_x - self.min_x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max_y - self.min_y + 1) as usize
+    }
+
+    pub fn contains(&self, p: Point2<i64>) -> bool {
+        (self.min_x..=self.max_x).contains(&p.x) && (self.min_y..=self.max_y).contains(&p.y)
+    }
+}
+
+// An unbounded grid that only stores the cells that were set, for maps that
+// grow in every direction. Positions may be negative.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point2<i64>, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Parses a character map like `Grid::parse`, keeping only the cells `f`
+    // returns something for. The first line is y = 0.
+    pub fn parse<F, E>(s: &str, mut f: F) -> Result<Self, InputError>
+    where
+        F: FnMut(char) -> Result<Option<T>, E>,
+        E: fmt::Display,
+    {
+        let dense = Grid::parse(s, &mut f)?;
+        let width = dense.width;
+
+        Ok(dense
+            .cells
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                let p = Point2::new((i % width) as i64, (i / width) as i64);
+                Some((p, c?))
+            })
+            .collect())
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn insert(&mut self, p: Point2<i64>, value: T) -> Option<T> {
+        self.cells.insert(p, value)
+    }
+
+    pub fn remove(&mut self, p: Point2<i64>) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    pub fn get(&self, p: Point2<i64>) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Point2<i64>) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    pub fn entry(&mut self, p: Point2<i64>) -> Entry<'_, Point2<i64>, T> {
+        self.cells.entry(p)
+    }
+
+    pub fn contains(&self, p: Point2<i64>) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    // The set cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<i64>, &T)> {
+        self.cells.iter().map(|(p, c)| (*p, c))
+    }
+
+    pub fn bounds(&self) -> Option<Bounds> {
+        let mut keys = self.cells.keys();
+        let first = keys.next()?;
+
+        Some(keys.fold(
+            Bounds {
+                min_x: first.x,
+                max_x: first.x,
+                min_y: first.y,
+                max_y: first.y,
+            },
+            |b, p| Bounds {
+                min_x: b.min_x.min(p.x),
+                max_x: b.max_x.max(p.x),
+                min_y: b.min_y.min(p.y),
+                max_y: b.max_y.max(p.y),
+            },
+        ))
+    }
+
+    // The span of the bounds along x and y, empty if nothing is set. Finding
+    // the bounds visits every cell, so callers work this out once.
+    fn spans(&self) -> (RangeInclusive<i64>, RangeInclusive<i64>) {
+        let ((x0, x1), (y0, y1)) = self.bounds().map_or(((1, 0), (1, 0)), |b| {
+            ((b.min_x, b.max_x), (b.min_y, b.max_y))
+        });
+
+        (x0..=x1, y0..=y1)
+    }
+
+    // The row at `y` across the bounds, with gaps for the cells never set.
+    pub fn row(&self, y: i64) -> impl Iterator<Item = Option<&T>> {
+        let (xs, _) = self.spans();
+        xs.map(move |x| self.get(Point2::new(x, y)))
+    }
+
+    // Every row from the smallest y to the largest.
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = Option<&T>>> {
+        let (xs, ys) = self.spans();
+        ys.map(move |y| xs.clone().map(move |x| self.get(Point2::new(x, y))))
+    }
+
+    pub fn column(&self, x: i64) -> impl Iterator<Item = Option<&T>> {
+        let (_, ys) = self.spans();
+        ys.map(move |y| self.get(Point2::new(x, y)))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = Option<&T>>> {
+        let (xs, ys) = self.spans();
+        xs.map(move |x| ys.clone().map(move |y| self.get(Point2::new(x, y))))
+    }
+
+    // The set cells sharing an edge with `p`.
+    pub fn neighbours4(&self, p: Point2<i64>) -> impl Iterator<Item = (Point2<i64>, &T)> {
+        self.around(p, &ORTHOGONAL)
+    }
+
+    // The set cells surrounding `p`, diagonals included.
+    pub fn neighbours8(&self, p: Point2<i64>) -> impl Iterator<Item = (Point2<i64>, &T)> {
+        self.around(p, &SURROUNDING)
+    }
+
+    fn around<'a>(
+        &'a self,
+        p: Point2<i64>,
+        offsets: &'a [(i64, i64)],
+    ) -> impl Iterator<Item = (Point2<i64>, &'a T)> {
+        offsets.iter().filter_map(move |o| {
+            let n = p + Point2::from(*o);
+            self.cells.get(&n).map(|c| (n, c))
+        })
+    }
+
+    // Draws the bounds a row per line, smallest y first. `f` is handed `None`
+    // for the cells that were never set.
+    pub fn render<F: Fn(Option<&T>) -> char>(&self, f: F) -> String {
+        self.rows()
+            .map(|row| row.map(&f).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    // Mirrors the grid top to bottom, for maps where y points up.
+    pub fn flip_y(self) -> Self {
+        Self {
+            cells: self
+                .cells
+                .into_iter()
+                .map(|(p, c)| (Point2::new(p.x, -p.y), c))
+                .collect(),
+        }
+    }
+}
+
+impl<T> FromIterator<(Point2<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point2<i64>, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MAP: &str = "#..\n.#.\n..#\n#..\n";
+
+    fn map() -> Grid<bool> {
+        Grid::parse(MAP, |c| match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("unknown cell"),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let g = map();
+
+        assert_eq!((g.width(), g.height()), (3, 4));
+        assert!(g[(2, 2)] && !g[(2, 1)]);
+        assert_eq!(g.render(|c| if *c { '#' } else { '.' }), MAP.trim_end());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let bool = |c| match c {
+            '#' | '.' => Ok(c == '#'),
+            _ => Err("unknown cell"),
+        };
+
+        assert!(matches!(
+            Grid::parse("#.\n#\n", bool),
+            Err(InputError::Parse { index: 1, .. })
+        ));
+        assert!(matches!(
+            Grid::parse("#.\n#x\n", bool),
+            Err(InputError::Parse { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let g = Grid::from_vec(3, (0..6).collect()).unwrap();
+
+        assert_eq!(g.rows().collect::<Vec<_>>(), [[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(g.column(1).copied().collect::<Vec<_>>(), [1, 4]);
+        assert_eq!(g.columns().count(), 3);
+        assert_eq!(Grid::from_vec(4, vec![0; 6]), None);
+    }
+
+    #[test]
+    fn neighbours() {
+        let g = map();
+        let n4: Vec<_> = g.neighbours4(0, 0).map(|(p, _)| p).collect();
+        let n8: Vec<_> = g
+            .neighbours8(1, 1)
+            .filter(|(_, c)| **c)
+            .map(|(p, _)| p)
+            .collect();
+
+        assert_eq!(n4, [(1, 0), (0, 1)]);
+        assert_eq!(n8, [(2, 2), (0, 0)]);
+        assert!(!g.in_bounds(-1, 0) && !g.in_bounds(3, 0) && g.in_bounds(2, 3));
+    }
+
+    #[test]
+    fn sparse() {
+        let mut g = SparseGrid::new();
+        g.insert(Point2::new(-1, 2), 'a');
+        g.insert(Point2::new(1, -1), 'b');
+
+        assert_eq!(
+            g.bounds(),
+            Some(Bounds {
+                min_x: -1,
+                max_x: 1,
+                min_y: -1,
+                max_y: 2,
+            })
+        );
+        assert_eq!(g.render(|c| *c.unwrap_or(&' ')), "  b\n   \n   \na  ");
+        assert_eq!(
+            g.clone().flip_y().render(|c| *c.unwrap_or(&' ')),
+            "a  \n   \n   \n  b"
+        );
+        assert_eq!(g.neighbours8(Point2::default()).count(), 1);
+        assert_eq!(SparseGrid::<char>::new().render(|_| '#'), "");
+    }
+
+    #[test]
+    fn sparse_parse() {
+        let g = SparseGrid::parse(MAP, |c| Ok::<_, String>(Some(c).filter(|c| *c == '#'))).unwrap();
+
+        assert_eq!(g.len(), 4);
+        assert!(g.contains(Point2::new(0, 3)));
+        assert_eq!(g.column(0).filter(Option::is_some).count(), 2);
+    }
+}
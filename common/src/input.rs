@@ -0,0 +1,307 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// Overrides the input file of whichever day is run.
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+#[derive(Debug)]
+pub enum InputError {
+    Io(io::Error),
+    File(PathBuf, io::Error),
+    Parse {
+        index: usize,
+        record: String,
+        reason: String,
+    },
+    // The records parsed, but don't make up a valid input together.
+    Invalid(String),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io(e) => write!(f, "unable to read input: {}", e),
+            InputError::File(path, e) => write!(f, "unable to read {}: {}", path.display(), e),
+            InputError::Parse {
+                index,
+                record,
+                reason,
+            } => write!(f, "record {} ({:?}): {}", index, record, reason),
+            InputError::Invalid(reason) => write!(f, "invalid input: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl From<io::Error> for InputError {
+    fn from(e: io::Error) -> Self {
+        InputError::Io(e)
+    }
+}
+
+// How the input is cut into records. Empty records are always dropped.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Separator {
+    // Splits on the string and strips every line break from the records,
+    // which suits single-line formats such as comma separated values.
+    Split(String),
+
+    // Blocks of lines separated by blank lines. Records keep their interior
+    // newlines, normalized to `\n`, but not the trailing one.
+    Paragraphs,
+
+    // One record per line, accepting both `\n` and `\r\n` line endings.
+    Lines,
+
+    // Splits on the string and leaves the records exactly as they are.
+    Raw(String),
+}
+
+impl From<&str> for Separator {
+    fn from(s: &str) -> Self {
+        Separator::Split(s.to_string())
+    }
+}
+
+impl From<char> for Separator {
+    fn from(c: char) -> Self {
+        Separator::Split(c.to_string())
+    }
+}
+
+// Where a day's input comes from.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    // Picks the first of: `path`, the file named by `AOC_INPUT`, or the input
+    // file shipped in `manifest_dir`. Falls back to stdin when none of them
+    // apply, and either of the first two can be `-` to ask for it.
+    pub fn resolve(path: Option<&Path>, manifest_dir: Option<&Path>) -> Self {
+        path.map(Self::path)
+            .or_else(Self::from_env)
+            .or_else(|| manifest_dir.and_then(Self::shipped))
+            .unwrap_or(Source::Stdin)
+    }
+
+    // The input `AOC_INPUT` names, if it's set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(INPUT_VAR)
+            .filter(|p| !p.is_empty())
+            .map(|p| Self::path(Path::new(&p)))
+    }
+
+    // A file, or stdin for `-`.
+    pub fn path(path: &Path) -> Self {
+        match path == Path::new("-") {
+            true => Source::Stdin,
+            false => Source::File(path.to_path_buf()),
+        }
+    }
+
+    // The input file shipped in `manifest_dir`, either `input` or
+    // `input/input.txt`.
+    pub fn shipped(manifest_dir: &Path) -> Option<Self> {
+        vec![
+            manifest_dir.join("input"),
+            manifest_dir.join("input").join("input.txt"),
+        ]
+        .into_iter()
+        .find(|p| p.is_file())
+        .map(Source::File)
+    }
+
+    // The input for the crate in `manifest_dir`, which is usually
+    // `env!("CARGO_MANIFEST_DIR")`. Works from binaries and tests alike.
+    pub fn for_crate(manifest_dir: &str) -> Self {
+        Self::resolve(None, Some(Path::new(manifest_dir)))
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::File(p) => {
+                std::fs::read_to_string(p).map_err(|e| InputError::File(p.clone(), e))
+            }
+            Source::Stdin => read_stdin(),
+        }
+    }
+}
+
+pub fn read_stdin() -> Result<String, InputError> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+pub fn records<S: Into<Separator>>(input: &str, separator: S) -> Vec<String> {
+    let records: Vec<String> = match separator.into() {
+        Separator::Split(s) => input
+            .split(s.as_str())
+            .map(|r| r.replace(['\r', '\n'], ""))
+            .collect(),
+        Separator::Paragraphs => paragraphs(input),
+        Separator::Lines => input.lines().map(String::from).collect(),
+        Separator::Raw(s) => input.split(s.as_str()).map(String::from).collect(),
+    };
+
+    records.into_iter().filter(|r| !r.is_empty()).collect()
+}
+
+fn paragraphs(input: &str) -> Vec<String> {
+    let mut ret = vec![String::new()];
+
+    for line in input.lines() {
+        let current = ret.last_mut().unwrap();
+
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                ret.push(String::new());
+            }
+        } else {
+            if !current.is_empty() {
+                current.push('\n');
+            }
+
+            current.push_str(line);
+        }
+    }
+
+    ret
+}
+
+// Maps every record through `f`, stopping at the first one it rejects.
+pub fn parse_with<S, T, E, F>(input: &str, separator: S, mut f: F) -> Result<Vec<T>, InputError>
+where
+    S: Into<Separator>,
+    E: fmt::Display,
+    F: FnMut(&str) -> Result<T, E>,
+{
+    records(input, separator)
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| {
+            f(&record).map_err(|e| InputError::Parse {
+                index,
+                reason: e.to_string(),
+                record,
+            })
+        })
+        .collect()
+}
+
+pub fn parse<S, T>(input: &str, separator: S) -> Result<Vec<T>, InputError>
+where
+    S: Into<Separator>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    parse_with(input, separator, str::parse)
+}
+
+// Reads the source and splits it into records.
+pub fn load_records<S>(source: &Source, separator: S) -> Result<Vec<String>, InputError>
+where
+    S: Into<Separator>,
+{
+    Ok(records(&source.read()?, separator))
+}
+
+// Reads the source and parses every record as a `T`.
+pub fn load<S, T>(source: &Source, separator: S) -> Result<Vec<T>, InputError>
+where
+    S: Into<Separator>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    parse(&source.read()?, separator)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split() {
+        assert_eq!(records("1,2,\n3\n", ','), vec!["1", "2", "3"]);
+        assert_eq!(records("ab\n", ""), vec!["a", "b"]);
+        assert_eq!(records("a\r\nb\r\n", "\n"), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn resolve() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let explicit = Path::new("/some/input.txt");
+
+        assert_eq!(
+            Source::resolve(Some(explicit), Some(dir)),
+            Source::File(explicit.to_path_buf())
+        );
+        assert_eq!(
+            Source::resolve(Some(Path::new("-")), Some(dir)),
+            Source::Stdin
+        );
+
+        // common doesn't ship an input of its own
+        if std::env::var_os(INPUT_VAR).is_none() {
+            assert_eq!(Source::resolve(None, Some(dir)), Source::Stdin);
+        }
+    }
+
+    #[test]
+    fn missing_file() {
+        let err = Source::File(PathBuf::from("/no/such/input"))
+            .read()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("unable to read /no/such/input: "));
+    }
+
+    #[test]
+    fn paragraphs() {
+        let input = "a\r\nb\r\n\r\n\r\nc\n  \nd\ne\n";
+
+        assert_eq!(
+            records(input, Separator::Paragraphs),
+            vec!["a\nb", "c", "d\ne"]
+        );
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(records("a b\r\n\nc\n", Separator::Lines), vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn raw() {
+        let input = "a\nb;c\n;\n";
+
+        assert_eq!(
+            records(input, Separator::Raw(";".to_string())),
+            vec!["a\nb", "c\n", "\n"]
+        );
+    }
+
+    #[test]
+    fn parse_error() {
+        let err = parse::<_, i64>("1\n2\nx3\n", '\n').unwrap_err();
+
+        match &err {
+            InputError::Parse { index, record, .. } => {
+                assert_eq!((*index, record.as_str()), (2, "x3"));
+            }
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        assert_eq!(
+            err.to_string(),
+            "record 2 (\"x3\"): invalid digit found in string"
+        );
+    }
+}
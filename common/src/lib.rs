@@ -1,18 +1,43 @@
+pub mod answers;
+pub mod bench;
+pub mod grid;
+pub mod input;
+pub mod ocr;
+pub mod point;
+pub mod solution;
+
+// Reads the crate's input (see `input::Source::resolve`) and splits it into
+// records, panicking with the offending record if any of them fails to parse.
+// The separator is anything that converts into an `input::Separator`: a
+// string or char splits on it, or pass one of the other modes directly. See
+// `input` for the non-panicking functions this wraps.
 #[macro_export]
 macro_rules! load_input {
     () => (load_input!(""));
+    (@read) => {
+        $crate::input::Source::for_crate(env!("CARGO_MANIFEST_DIR"))
+            .read()
+            .unwrap_or_else(|e| panic!("{}", e))
+    };
     ($separator:expr) => (load_input!($separator, String::from));
-    ($separator:expr, $ty:ident) => (load_input!($separator, |n| n.parse::<$ty>().unwrap()));
-    ($separator:expr, $( $mapper:expr ),*) => {{
-        let mut input = std::string::String::new();
-        std::io::Read::read_to_string(&mut std::io::stdin(), &mut input)
-            .expect("unable to read stdin");
-
-        input
-            .split($separator)
-            .map(|l| l.replace(&['\n'][..], ""))
-            .filter(|l| l.len() > 0)
+    ($separator:expr, $ty:ident) => {
+        $crate::input::parse::<_, $ty>(&load_input!(@read), $separator)
+            .unwrap_or_else(|e| panic!("{}", e))
+            .into_iter()
+            .collect()
+    };
+    ($separator:expr, $( $mapper:expr ),*) => {
+        $crate::input::records(&load_input!(@read), $separator)
+            .into_iter()
             $(.map($mapper))*
             .collect()
-    }};
+    };
+}
+
+// Reads the crate's input as a single string, untouched.
+#[macro_export]
+macro_rules! read_input {
+    () => {
+        $crate::load_input!(@read)
+    };
 }
@@ -0,0 +1,245 @@
+use crate::grid::Grid;
+use std::fmt;
+
+type Font = &'static [(char, &'static [&'static str])];
+
+// The letters puzzles draw six pixels tall, four wide apart from `Y`.
+const SMALL: Font = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+// The letters puzzles draw ten pixels tall and six wide.
+#[rustfmt::skip]
+const LARGE: Font = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#",
+            "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....",
+            "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######",
+            "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.",
+            "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....",
+            "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#",
+            "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.",
+            "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..",
+            "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..",
+            "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OcrError {
+    // The lit pixels span a height neither font uses.
+    Height(usize),
+    // The x of the leftmost column of each glyph that isn't a letter.
+    Unrecognized(Vec<usize>),
+}
+
+impl fmt::Display for OcrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OcrError::Height(h) => write!(f, "expected letters 6 or 10 pixels tall, found {}", h),
+            OcrError::Unrecognized(xs) => {
+                let xs: Vec<String> = xs.iter().map(usize::to_string).collect();
+                write!(f, "unrecognized glyphs at x = {}", xs.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for OcrError {}
+
+// Reads the letters drawn by the lit pixels of `image`. Glyphs are told apart
+// by the blank columns between them, and blank rows and columns around the
+// text are ignored, so it needn't be aligned to anything. A wide `Y` fills
+// its whole cell, so runs of columns that aren't a single letter are split
+// into several where that reads.
+pub fn recognize(image: &Grid<bool>) -> Result<String, OcrError> {
+    let lit: Vec<usize> = (0..image.height())
+        .filter(|y| image.row(*y).contains(&true))
+        .collect();
+
+    let (top, bottom) = match (lit.first(), lit.last()) {
+        (Some(top), Some(bottom)) => (*top, *bottom),
+        _ => return Ok(String::new()),
+    };
+
+    let font: Vec<(char, Vec<String>)> = match bottom - top + 1 {
+        6 => SMALL,
+        10 => LARGE,
+        h => return Err(OcrError::Height(h)),
+    }
+    .iter()
+    .map(|(c, rows)| (*c, trim(rows)))
+    .collect();
+
+    let blank = |x: usize| (top..=bottom).all(|y| !image[(x, y)]);
+    let mut text = String::new();
+    let mut unrecognized = vec![];
+    let mut x = 0;
+
+    while x < image.width() {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < image.width() && !blank(x) {
+            x += 1;
+        }
+
+        let run: Vec<String> = (top..=bottom)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if image[(x, y)] { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+
+        match read(&font, &run, 0) {
+            Some(letters) => text.push_str(&letters),
+            None => unrecognized.push(start),
+        }
+    }
+
+    match unrecognized.is_empty() {
+        true => Ok(text),
+        false => Err(OcrError::Unrecognized(unrecognized)),
+    }
+}
+
+// Reads the letters in a rendered image, where `#` is lit and anything else,
+// including the end of a short line, is not.
+pub fn parse(image: &str) -> Result<String, OcrError> {
+    let lines: Vec<&str> = image.lines().collect();
+    let width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    let cells = lines
+        .iter()
+        .flat_map(|l| l.chars().chain(std::iter::repeat(' ')).take(width))
+        .map(|c| c == '#')
+        .collect();
+
+    recognize(&Grid::from_vec(width, cells).unwrap())
+}
+
+// Reads the letters filling `run` from column `from` onwards, or `None` if
+// there's no way to cut it into glyphs of `font`.
+fn read(font: &[(char, Vec<String>)], run: &[String], from: usize) -> Option<String> {
+    let width = run[0].len();
+    if from == width {
+        return Some(String::new());
+    }
+
+    font.iter().find_map(|(c, glyph)| {
+        let to = from + glyph[0].len();
+        if to > width || run.iter().zip(glyph).any(|(r, g)| &r[from..to] != g) {
+            return None;
+        }
+
+        read(font, run, to).map(|rest| format!("{}{}", c, rest))
+    })
+}
+
+// Drops the blank columns either side of a glyph in the font.
+fn trim(rows: &[&str]) -> Vec<String> {
+    let lit = |x: &usize| rows.iter().any(|r| r.as_bytes()[*x] == b'#');
+    let start = (0..rows[0].len()).find(lit).unwrap_or(0);
+    let end = (0..rows[0].len()).rfind(lit).map_or(start, |x| x + 1);
+
+    rows.iter().map(|r| r[start..end].to_string()).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Draws `text` the way puzzles do, with each letter in a cell `cell`
+    // columns wide.
+    fn draw(font: Font, text: &str, cell: usize) -> String {
+        let height = font[0].1.len();
+
+        (0..height)
+            .map(|y| {
+                let row: String = text
+                    .chars()
+                    .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1[y])
+                    .map(|r| format!("{:.<1$}", r, cell).replace('.', " "))
+                    .collect();
+
+                format!("{}\n", row)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small() {
+        let letters: String = SMALL.iter().map(|(c, _)| c).collect();
+        assert_eq!(parse(&draw(SMALL, &letters, 5)).unwrap(), letters);
+
+        // surrounded by blank rows and columns
+        let image: String = draw(SMALL, "HI", 5)
+            .lines()
+            .map(|l| format!("  {}\n", l))
+            .collect();
+        assert_eq!(parse(&format!("\n{}\n", image)).unwrap(), "HI");
+    }
+
+    #[test]
+    fn wide() {
+        // nothing separates a `Y` from the letter after it
+        assert_eq!(parse(&draw(SMALL, "YHYY", 5)).unwrap(), "YHYY");
+        assert_eq!(parse(&draw(SMALL, "AYI", 5)).unwrap(), "AYI");
+    }
+
+    #[test]
+    fn large() {
+        let letters: String = LARGE.iter().map(|(c, _)| c).collect();
+        assert_eq!(parse(&draw(LARGE, &letters, 8)).unwrap(), letters);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse(""), Ok(String::new()));
+        assert_eq!(parse("#\n#\n#"), Err(OcrError::Height(3)));
+
+        let mut image = draw(SMALL, "ABC", 5);
+        image.replace_range(8..9, "#");
+        image.replace_range(10..11, "#");
+        assert_eq!(parse(&image), Err(OcrError::Unrecognized(vec![5, 10])));
+    }
+}
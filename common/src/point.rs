@@ -0,0 +1,314 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+// A position or offset on a plane. Like `grid`, y grows downwards, so `Up`
+// is towards smaller y.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    // The number of orthogonal steps between the two points.
+    pub fn manhattan(self, other: Self) -> T {
+        diff(self.x, other.x) + diff(self.y, other.y)
+    }
+
+    // The number of steps between the two points when diagonals are allowed.
+    pub fn chebyshev(self, other: Self) -> T {
+        max(diff(self.x, other.x), diff(self.y, other.y))
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        diff(self.x, other.x) + diff(self.y, other.y) + diff(self.z, other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        max(
+            max(diff(self.x, other.x), diff(self.y, other.y)),
+            diff(self.z, other.z),
+        )
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(p: Point2<T>) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(p: Point3<T>) -> Self {
+        (p.x, p.y, p.z)
+    }
+}
+
+// Component-wise arithmetic, plus scaling by a scalar.
+macro_rules! ops {
+    ($point:ident, $( $field:ident ),+) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self { $( $field: self.$field + other.$field ),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self { $( $field: self.$field - other.$field ),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, n: T) -> Self {
+                Self { $( $field: self.$field * n ),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $( $field: -self.$field ),+ }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: Self) {
+                $( self.$field += other.$field; )+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: Self) {
+                $( self.$field -= other.$field; )+
+            }
+        }
+    };
+}
+
+ops!(Point2, x, y);
+ops!(Point3, x, y, z);
+
+fn diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if a < b {
+        b
+    } else {
+        a
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    // Clockwise from up, in the same order as `grid::ORTHOGONAL`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    // A single step in this direction, with y growing downwards.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        };
+
+        Point2::new(T::from(x), T::from(y))
+    }
+
+    // `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    // `U`, `R`, `D` or `L`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Direction::Up),
+            'R' => Some(Direction::Right),
+            'D' => Some(Direction::Down),
+            'L' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    // `N`, `E`, `S` or `W`, with north being up.
+    pub fn from_compass(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Direction::Up),
+            'E' => Some(Direction::Right),
+            'S' => Some(Direction::Down),
+            'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+}
+
+// Accepts a single character in any of the notations above. Puzzles stick to
+// one, so prefer the specific parser when the others would be an error.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_arrow(c)
+                .or_else(|| Self::from_letter(c))
+                .or_else(|| Self::from_compass(c)),
+            _ => None,
+        }
+        .ok_or_else(|| format!("invalid direction {:?}", s))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point2::new(1, -2) + Point2::new(3, 4);
+        assert_eq!(p, Point2::new(4, 2));
+
+        p -= Point2::new(1, 1);
+        assert_eq!(-(p * 2), Point2::new(-6, -2));
+        assert_eq!(
+            Point3::new(1, 2, 3) - Point3::new(3, 2, 1),
+            Point3::new(-2, 0, 2)
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1, 5);
+        let b = Point2::new(4, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+
+        // unsigned coordinates never go negative
+        assert_eq!(Point2::new(0u32, 9).manhattan(Point2::new(3, 2)), 10);
+        assert_eq!(Point3::new(-1, 2, 0).chebyshev(Point3::new(1, -3, 4)), 5);
+    }
+
+    #[test]
+    fn turns() {
+        for d in Direction::ALL.iter() {
+            assert_eq!(d.turn_left().turn_right(), *d);
+            assert_eq!(d.turn_right().turn_right(), d.reverse());
+            assert_eq!(d.offset::<i64>() + d.reverse().offset(), Point2::default());
+        }
+
+        let offsets: Vec<(i64, i64)> = Direction::ALL.iter().map(|d| d.offset().into()).collect();
+        assert_eq!(offsets, crate::grid::ORTHOGONAL);
+    }
+
+    #[test]
+    fn parse() {
+        let parsed: Result<Vec<Direction>, _> = "^ R S <".split(' ').map(str::parse).collect();
+        assert_eq!(
+            parsed.unwrap(),
+            [
+                Direction::Up,
+                Direction::Right,
+                Direction::Down,
+                Direction::Left
+            ]
+        );
+
+        assert_eq!(Direction::from_arrow('U'), None);
+        assert!("x".parse::<Direction>().is_err());
+        assert!("UD".parse::<Direction>().is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use crate::input::{InputError, Source};
+use std::fmt::{self, Display};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+// A day's puzzle. The input is parsed once and both parts share the result.
+pub trait Solution {
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, InputError>;
+    fn part1(input: &Self::Input) -> impl Display;
+    fn part2(input: &Self::Input) -> impl Display;
+}
+
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub value: String,
+    pub elapsed: Duration,
+}
+
+// The answers to both parts, and how long parsing took.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub parse: Duration,
+    pub parts: [Answer; 2],
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "parse  ({:.2?})", self.parse)?;
+
+        for (i, answer) in self.parts.iter().enumerate() {
+            write!(f, "part {} ({:.2?}):", i + 1, answer.elapsed)?;
+
+            // multi-line answers, like rendered images, start on their own line
+            match answer.value.contains('\n') {
+                true => writeln!(f, "\n{}", answer.value.trim_end())?,
+                false => writeln!(f, " {}", answer.value)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Parses `input` and runs both parts of `S` on it, timing each phase.
+pub fn solve<S: Solution>(input: &str) -> Result<Report, InputError> {
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    Ok(Report {
+        parse,
+        parts: [
+            time(|| S::part1(&parsed).to_string()),
+            time(|| S::part2(&parsed).to_string()),
+        ],
+    })
+}
+
+// Solves `S` for the input of the crate being run and prints the report,
+// exiting with an error if the input can't be read or parsed. The crate is
+// found through the `CARGO_MANIFEST_DIR` that `cargo run` sets, so binaries
+// run some other way read `AOC_INPUT` or stdin.
+pub fn run<S: Solution>() {
+    let dir = std::env::var_os("CARGO_MANIFEST_DIR");
+    let source = Source::resolve(None, dir.as_deref().map(Path::new));
+
+    match source.read().and_then(|input| solve::<S>(&input)) {
+        Ok(report) => print!("{}", report),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn time<F: FnOnce() -> String>(f: F) -> Answer {
+    let start = Instant::now();
+    let value = f();
+
+    Answer {
+        value,
+        elapsed: start.elapsed(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::input;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Input, InputError> {
+            input::parse(input, ',')
+        }
+
+        fn part1(input: &Self::Input) -> impl Display {
+            input.iter().sum::<u32>()
+        }
+
+        fn part2(input: &Self::Input) -> impl Display {
+            input.iter().map(|n| format!("{}\n", n)).collect::<String>()
+        }
+    }
+
+    #[test]
+    fn report() {
+        let report = solve::<Sum>("1,2,3").unwrap();
+        assert_eq!(report.parts[0].value, "6");
+
+        let lines: Vec<String> = report.to_string().lines().map(String::from).collect();
+        assert!(lines[1].starts_with("part 1 (") && lines[1].ends_with("): 6"));
+        assert!(lines[2].ends_with("):"));
+        assert_eq!(lines[3..], ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(matches!(
+            solve::<Sum>("1,x"),
+            Err(InputError::Parse { index: 1, .. })
+        ));
+    }
+}